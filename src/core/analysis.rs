@@ -0,0 +1,675 @@
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use super::error::EdError;
+use super::network::{EDNetwork, ERROR_COUNT_THRESHOLD, LayerType, LearningStats};
+use super::training::TrainingPattern;
+
+/// Default memory budget of an `OrderRecorder` (1 MiB)
+pub const DEFAULT_ORDER_MEMORY_CAP: usize = 1 << 20;
+
+/// Number of leading epochs averaged by `order_correlation`
+pub const DEFAULT_EARLY_EPOCHS: usize = 10;
+
+/// Flat permutation storage, u16 indices whenever they fit
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum OrderStorage {
+    Compact(Vec<u16>),
+    Wide(Vec<u32>),
+}
+
+impl OrderStorage {
+    fn len(&self) -> usize {
+        match self {
+            OrderStorage::Compact(indices) => indices.len(),
+            OrderStorage::Wide(indices) => indices.len(),
+        }
+    }
+
+    fn index_bytes(&self) -> usize {
+        match self {
+            OrderStorage::Compact(_) => 2,
+            OrderStorage::Wide(_) => 4,
+        }
+    }
+
+    fn get(&self, position: usize) -> usize {
+        match self {
+            OrderStorage::Compact(indices) => indices[position] as usize,
+            OrderStorage::Wide(indices) => indices[position] as usize,
+        }
+    }
+
+    fn extend(&mut self, order: &[usize]) {
+        match self {
+            OrderStorage::Compact(indices) => indices.extend(order.iter().map(|&i| i as u16)),
+            OrderStorage::Wide(indices) => indices.extend(order.iter().map(|&i| i as u32)),
+        }
+    }
+
+    /// Keep only the permutations at the given record slots
+    fn retain_records(&mut self, pattern_count: usize, keep: &[bool]) {
+        fn retain<T: Copy>(indices: &mut Vec<T>, pattern_count: usize, keep: &[bool]) {
+            let mut write = 0;
+            for (record, _) in keep.iter().enumerate().filter(|(_, kept)| **kept) {
+                indices.copy_within(record * pattern_count..(record + 1) * pattern_count, write);
+                write += pattern_count;
+            }
+            indices.truncate(write);
+        }
+
+        match self {
+            OrderStorage::Compact(indices) => retain(indices, pattern_count, keep),
+            OrderStorage::Wide(indices) => retain(indices, pattern_count, keep),
+        }
+    }
+}
+
+/// Opt-in observer storing the per-epoch pattern presentation order
+///
+/// Permutations are stored as u16 indices when `pattern_count < 65536`. When the
+/// memory cap is reached the recording stride doubles and already stored epochs
+/// off the new stride are dropped, so epoch 1 is always kept.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct OrderRecorder {
+    pattern_count: usize,
+    max_bytes: usize,
+    stride: usize,
+    epochs: Vec<usize>,
+    orders: OrderStorage,
+}
+
+impl OrderRecorder {
+    /// Create a recorder with the default memory cap
+    pub fn new(pattern_count: usize) -> Self {
+        Self::with_memory_cap(pattern_count, DEFAULT_ORDER_MEMORY_CAP)
+    }
+
+    /// Create a recorder whose stored permutations stay within `max_bytes`
+    pub fn with_memory_cap(pattern_count: usize, max_bytes: usize) -> Self {
+        let orders = if pattern_count <= u16::MAX as usize {
+            OrderStorage::Compact(Vec::new())
+        } else {
+            OrderStorage::Wide(Vec::new())
+        };
+
+        Self {
+            pattern_count,
+            max_bytes,
+            stride: 1,
+            epochs: Vec::new(),
+            orders,
+        }
+    }
+
+    /// Record the order in which patterns were presented during `epoch` (1-based, as in `LearningStats`)
+    ///
+    /// Epochs off the current stride are ignored.
+    pub fn record(&mut self, epoch: usize, order: &[usize]) -> Result<(), EdError> {
+        if order.len() != self.pattern_count {
+            return Err(EdError::DimensionMismatch {
+                expected: self.pattern_count,
+                got: order.len(),
+            });
+        }
+
+        let mut seen = vec![false; self.pattern_count];
+        for &index in order {
+            if index >= self.pattern_count || std::mem::replace(&mut seen[index], true) {
+                return Err(EdError::InvalidConfig(format!(
+                    "presentation order is not a permutation of 0..{}",
+                    self.pattern_count
+                )));
+            }
+        }
+
+        if !self.on_stride(epoch) {
+            return Ok(());
+        }
+
+        while self.epochs.len() > 1 && self.over_cap() {
+            self.subsample();
+        }
+        // A cap too small for two permutations keeps only the first one
+        if !self.on_stride(epoch) || (!self.epochs.is_empty() && self.over_cap()) {
+            return Ok(());
+        }
+
+        self.epochs.push(epoch);
+        self.orders.extend(order);
+
+        Ok(())
+    }
+
+    /// Number of patterns in each recorded permutation
+    pub fn pattern_count(&self) -> usize {
+        self.pattern_count
+    }
+
+    /// Current recording stride in epochs
+    pub fn stride(&self) -> usize {
+        self.stride
+    }
+
+    /// Epoch numbers that have a stored permutation, ascending
+    pub fn epochs(&self) -> &[usize] {
+        &self.epochs
+    }
+
+    /// Permutation stored at record slot `record`
+    ///
+    /// Panics if `record` is not below `epochs().len()`.
+    pub fn order(&self, record: usize) -> Vec<usize> {
+        let start = record * self.pattern_count;
+        (start..start + self.pattern_count)
+            .map(|position| self.orders.get(position))
+            .collect()
+    }
+
+    /// Stored `(epoch, permutation)` pairs in epoch order
+    pub fn iter(&self) -> impl Iterator<Item = (usize, Vec<usize>)> + '_ {
+        self.epochs.iter().enumerate().map(|(record, &epoch)| (epoch, self.order(record)))
+    }
+
+    /// Bytes used by the stored epochs and permutations
+    pub fn memory_bytes(&self) -> usize {
+        self.epochs.len() * std::mem::size_of::<usize>() + self.orders.len() * self.orders.index_bytes()
+    }
+
+    /// One row per recorded epoch: the epoch followed by the pattern shown at each position
+    pub fn to_csv(&self) -> String {
+        let mut csv = String::from("epoch");
+        for position in 0..self.pattern_count {
+            csv.push_str(&format!(",pos{}", position));
+        }
+        csv.push('\n');
+
+        for (epoch, order) in self.iter() {
+            csv.push_str(&epoch.to_string());
+            for index in order {
+                csv.push_str(&format!(",{}", index));
+            }
+            csv.push('\n');
+        }
+
+        csv
+    }
+
+    fn on_stride(&self, epoch: usize) -> bool {
+        epoch.saturating_sub(1).is_multiple_of(self.stride)
+    }
+
+    /// Whether storing one more permutation would exceed the memory cap
+    fn over_cap(&self) -> bool {
+        let record_bytes = std::mem::size_of::<usize>() + self.pattern_count * self.orders.index_bytes();
+        self.memory_bytes() + record_bytes > self.max_bytes
+    }
+
+    /// Double the stride and drop stored epochs that no longer fall on it
+    fn subsample(&mut self) {
+        self.stride *= 2;
+
+        let keep: Vec<bool> = self.epochs.iter().map(|&epoch| self.on_stride(epoch)).collect();
+        self.orders.retain_records(self.pattern_count, &keep);
+
+        let mut kept = keep.iter();
+        self.epochs.retain(|_| *kept.next().unwrap_or(&false));
+    }
+}
+
+/// Per-pattern error curves across epochs
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PatternCurveRecorder {
+    pattern_count: usize,
+    epochs: Vec<usize>,
+    /// Errors of every recorded epoch, indexed by pattern id
+    errors: Vec<Vec<f64>>,
+}
+
+impl PatternCurveRecorder {
+    /// Create an empty recorder for `pattern_count` patterns
+    pub fn new(pattern_count: usize) -> Self {
+        Self {
+            pattern_count,
+            epochs: Vec::new(),
+            errors: Vec::new(),
+        }
+    }
+
+    /// Record the absolute error of every pattern (indexed by pattern id) after `epoch`
+    pub fn record(&mut self, epoch: usize, errors: &[f64]) -> Result<(), EdError> {
+        if errors.len() != self.pattern_count {
+            return Err(EdError::DimensionMismatch {
+                expected: self.pattern_count,
+                got: errors.len(),
+            });
+        }
+
+        self.epochs.push(epoch);
+        self.errors.push(errors.to_vec());
+
+        Ok(())
+    }
+
+    /// Epoch numbers that have recorded errors
+    pub fn epochs(&self) -> &[usize] {
+        &self.epochs
+    }
+
+    /// Error curve of one pattern across the recorded epochs
+    ///
+    /// Panics if `pattern` is not below the recorder's pattern count.
+    pub fn curve(&self, pattern: usize) -> Vec<f64> {
+        self.errors.iter().map(|errors| errors[pattern]).collect()
+    }
+
+    /// First epoch from which the pattern's error stays within the misclassification threshold
+    ///
+    /// Panics if `pattern` is not below the recorder's pattern count.
+    pub fn solved_epoch(&self, pattern: usize) -> Option<usize> {
+        let mut solved = None;
+
+        for (epoch, errors) in self.epochs.iter().zip(&self.errors) {
+            if errors[pattern] > ERROR_COUNT_THRESHOLD {
+                solved = None;
+            } else if solved.is_none() {
+                solved = Some(*epoch);
+            }
+        }
+
+        solved
+    }
+}
+
+/// Early presentation position and solved epoch of one pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PatternOrderStats {
+    pub pattern: usize,
+    /// Average 0-based presentation position over the early epochs
+    pub mean_position: f64,
+    pub solved_epoch: Option<usize>,
+}
+
+/// Relation between early presentation order and when each pattern was solved
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct OrderCorrelationReport {
+    /// Number of leading recorded epochs averaged per pattern
+    pub early_epochs: usize,
+    pub patterns: Vec<PatternOrderStats>,
+    /// Pearson correlation of mean position against solved epoch over solved patterns,
+    /// `None` when fewer than two patterns were solved or either side is constant
+    pub correlation: Option<f64>,
+}
+
+/// Correlate each pattern's average position in the first `DEFAULT_EARLY_EPOCHS` recorded epochs with its solved epoch
+pub fn order_correlation(recorder: &OrderRecorder, curves: &PatternCurveRecorder) -> OrderCorrelationReport {
+    order_correlation_within(recorder, curves, DEFAULT_EARLY_EPOCHS)
+}
+
+/// `order_correlation` over the first `early_epochs` recorded epochs
+pub fn order_correlation_within(recorder: &OrderRecorder, curves: &PatternCurveRecorder, early_epochs: usize) -> OrderCorrelationReport {
+    let records = early_epochs.min(recorder.epochs().len());
+    let mut position_sums = vec![0.0; recorder.pattern_count()];
+
+    for record in 0..records {
+        for (position, pattern) in recorder.order(record).into_iter().enumerate() {
+            position_sums[pattern] += position as f64;
+        }
+    }
+
+    let patterns: Vec<PatternOrderStats> = position_sums
+        .iter()
+        .enumerate()
+        .map(|(pattern, sum)| PatternOrderStats {
+            pattern,
+            mean_position: if records == 0 {
+                0.0
+            } else {
+                sum / records as f64
+            },
+            solved_epoch: (pattern < curves.pattern_count).then(|| curves.solved_epoch(pattern)).flatten(),
+        })
+        .collect();
+
+    let (positions, solved): (Vec<f64>, Vec<f64>) = patterns
+        .iter()
+        .filter_map(|stats| stats.solved_epoch.map(|epoch| (stats.mean_position, epoch as f64)))
+        .unzip();
+    let correlation = if records == 0 {
+        None
+    } else {
+        pearson(&positions, &solved)
+    };
+
+    OrderCorrelationReport {
+        early_epochs: records,
+        patterns,
+        correlation,
+    }
+}
+
+/// Pearson correlation coefficient, `None` for fewer than two points or zero variance
+fn pearson(xs: &[f64], ys: &[f64]) -> Option<f64> {
+    if xs.len() < 2 {
+        return None;
+    }
+
+    let n = xs.len() as f64;
+    let mean_x = xs.iter().sum::<f64>() / n;
+    let mean_y = ys.iter().sum::<f64>() / n;
+    let (mut covariance, mut var_x, mut var_y) = (0.0, 0.0, 0.0);
+
+    for (x, y) in xs.iter().zip(ys) {
+        covariance += (x - mean_x) * (y - mean_y);
+        var_x += (x - mean_x).powi(2);
+        var_y += (y - mean_y).powi(2);
+    }
+
+    if var_x == 0.0 || var_y == 0.0 {
+        return None;
+    }
+
+    Some(covariance / (var_x * var_y).sqrt())
+}
+
+/// Result of greedily lesioning hidden neurons while accuracy holds
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CapacityReport {
+    /// Hidden neurons in the analysed network
+    pub trained_hidden: usize,
+    /// Hidden neurons left when no further lesion kept the baseline accuracy
+    pub sufficient_hidden: usize,
+    /// Accuracy of the unlesioned network in percent
+    pub baseline_accuracy: f64,
+    /// Global indices of the lesioned hidden neurons, in removal order
+    pub removal_order: Vec<usize>,
+    /// Accuracy after each removal, parallel to `removal_order`
+    pub accuracy_trajectory: Vec<f64>,
+}
+
+impl CapacityReport {
+    /// Clone of `network` with every neuron of `removal_order` lesioned
+    pub fn prune(&self, network: &EDNetwork) -> EDNetwork {
+        let mut pruned = network.clone();
+        for &neuron in &self.removal_order {
+            lesion(&mut pruned, neuron);
+        }
+        pruned
+    }
+}
+
+/// Find how many hidden neurons the network actually needs on `dataset`
+///
+/// Each step lesions the remaining hidden neuron whose removal raises the total error least,
+/// and stops once the best candidate would drop accuracy below the baseline. Lesions are
+/// applied to a scratch clone, so `network` is left untouched.
+pub fn capacity_report(network: &EDNetwork, dataset: &[TrainingPattern]) -> Result<CapacityReport, EdError> {
+    let hidden = network.layer_range(LayerType::Hidden);
+    let (_, baseline_accuracy) = score(network, dataset)?;
+
+    let mut scratch = network.clone();
+    let mut remaining: Vec<usize> = hidden.clone().collect();
+    let mut removal_order = Vec::new();
+    let mut accuracy_trajectory = Vec::new();
+
+    while !remaining.is_empty() {
+        let mut best: Option<(usize, f64, f64)> = None;
+
+        for (slot, &neuron) in remaining.iter().enumerate() {
+            let mut candidate = scratch.clone();
+            lesion(&mut candidate, neuron);
+
+            let (error, accuracy) = score(&candidate, dataset)?;
+            if best.is_none_or(|(_, best_error, _)| error < best_error) {
+                best = Some((slot, error, accuracy));
+            }
+        }
+
+        match best {
+            Some((slot, _, accuracy)) if accuracy >= baseline_accuracy => {
+                let neuron = remaining.remove(slot);
+                lesion(&mut scratch, neuron);
+                removal_order.push(neuron);
+                accuracy_trajectory.push(accuracy);
+            }
+            _ => break,
+        }
+    }
+
+    Ok(CapacityReport {
+        trained_hidden: hidden.len(),
+        sufficient_hidden: remaining.len(),
+        baseline_accuracy,
+        removal_order,
+        accuracy_trajectory,
+    })
+}
+
+/// Silence a neuron by disabling every connection into and out of it
+fn lesion(network: &mut EDNetwork, neuron: usize) {
+    for (from, row) in network.connections.iter_mut().enumerate() {
+        for (to, connection) in row.iter_mut().enumerate() {
+            if from == neuron || to == neuron {
+                connection.connection_enabled = false;
+                connection.weight = 0.0;
+            }
+        }
+    }
+}
+
+/// Summed absolute error and accuracy in percent of a network over `dataset`
+fn score(network: &EDNetwork, dataset: &[TrainingPattern]) -> Result<(f64, f64), EdError> {
+    if dataset.is_empty() {
+        return Err(EdError::EmptyTrainingSet);
+    }
+
+    let mut total_error = 0.0;
+    let mut correct = 0;
+
+    for pattern in dataset {
+        let outputs = network.predict(&pattern.inputs)?;
+        if outputs.len() != pattern.targets.len() {
+            return Err(EdError::DimensionMismatch {
+                expected: outputs.len(),
+                got: pattern.targets.len(),
+            });
+        }
+
+        let errors: Vec<f64> = outputs
+            .iter()
+            .zip(&pattern.targets)
+            .map(|(output, target)| (target - output).abs())
+            .collect();
+        total_error += errors.iter().sum::<f64>();
+        if errors.iter().all(|&error| error <= ERROR_COUNT_THRESHOLD) {
+            correct += 1;
+        }
+    }
+
+    Ok((total_error, 100.0 * correct as f64 / dataset.len() as f64))
+}
+
+/// History of `LearningStats` that can be turned into a `MetricSeries`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StatsField {
+    /// `error_history`, one value per training epoch
+    Error,
+    /// `validation_history`, one value per validated epoch
+    Validation,
+}
+
+/// How two series with different epochs are brought onto common epochs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Alignment {
+    /// Keep only the epochs recorded in both series
+    Truncate,
+    /// Keep every epoch of either series inside the overlapping range, linearly interpolating the gaps
+    #[default]
+    Interpolate,
+}
+
+/// Named metric indexed by epoch, from this crate's stats or an external log
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricSeries {
+    pub name: String,
+    /// Strictly increasing epoch numbers
+    epochs: Vec<usize>,
+    values: Vec<f64>,
+}
+
+/// Two series sampled at the same epochs
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AlignedSeries {
+    pub epochs: Vec<usize>,
+    pub left: Vec<f64>,
+    pub right: Vec<f64>,
+}
+
+impl MetricSeries {
+    /// Pair epochs with values, rejecting mismatched lengths or epochs that do not strictly increase
+    pub fn new(name: impl Into<String>, epochs: Vec<usize>, values: Vec<f64>) -> Result<Self, EdError> {
+        if epochs.len() != values.len() {
+            return Err(EdError::DimensionMismatch {
+                expected: epochs.len(),
+                got: values.len(),
+            });
+        }
+        if epochs.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(EdError::InvalidConfig("metric series epochs must strictly increase".into()));
+        }
+
+        Ok(Self {
+            name: name.into(),
+            epochs,
+            values,
+        })
+    }
+
+    /// Series of one stats history, numbered so its last value falls on `stats.epoch`
+    pub fn from_learning_stats(stats: &LearningStats, which: StatsField) -> Self {
+        let (name, history) = match which {
+            StatsField::Error => ("error", &stats.error_history),
+            StatsField::Validation => ("validation_error", &stats.validation_history),
+        };
+        let first = (stats.epoch + 1).saturating_sub(history.len()).max(1);
+
+        Self {
+            name: name.into(),
+            epochs: (first..first + history.len()).collect(),
+            values: history.clone(),
+        }
+    }
+
+    /// Read two columns, selected by header name, from a comma-separated file
+    ///
+    /// Rows with an empty value are skipped, so logs with missing epochs load as gaps.
+    pub fn from_csv<P: AsRef<Path>>(path: P, epoch_col: &str, value_col: &str) -> Result<Self, EdError> {
+        let text = std::fs::read_to_string(path)?;
+        let mut lines = text.lines().filter(|line| !line.trim().is_empty());
+
+        let header: Vec<&str> = lines.next().ok_or(EdError::EmptyTrainingSet)?.split(',').map(str::trim).collect();
+        let column = |name: &str| {
+            header
+                .iter()
+                .position(|&col| col == name)
+                .ok_or_else(|| EdError::InvalidConfig(format!("CSV has no column '{}'", name)))
+        };
+        let (epoch_index, value_index) = (column(epoch_col)?, column(value_col)?);
+
+        let mut rows = Vec::new();
+        for (number, line) in lines.enumerate() {
+            let fields: Vec<&str> = line.split(',').map(str::trim).collect();
+            let field = |index: usize| fields.get(index).copied().unwrap_or("");
+            let invalid = || EdError::SerializationError(format!("invalid number on CSV row {}", number + 2));
+
+            if field(value_index).is_empty() {
+                continue;
+            }
+            let epoch: usize = field(epoch_index).parse().map_err(|_| invalid())?;
+            let value: f64 = field(value_index).parse().map_err(|_| invalid())?;
+            rows.push((epoch, value));
+        }
+        rows.sort_by_key(|&(epoch, _)| epoch);
+
+        let (epochs, values) = rows.into_iter().unzip();
+        Self::new(value_col, epochs, values)
+    }
+
+    pub fn epochs(&self) -> &[usize] {
+        &self.epochs
+    }
+
+    pub fn values(&self) -> &[f64] {
+        &self.values
+    }
+
+    pub fn len(&self) -> usize {
+        self.epochs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.epochs.is_empty()
+    }
+
+    /// Value at `epoch`, linearly interpolated between recorded neighbours, `None` outside the recorded range
+    pub fn value_at(&self, epoch: usize) -> Option<f64> {
+        match self.epochs.binary_search(&epoch) {
+            Ok(index) => Some(self.values[index]),
+            Err(0) => None,
+            Err(index) if index == self.epochs.len() => None,
+            Err(index) => {
+                let (e0, e1) = (self.epochs[index - 1] as f64, self.epochs[index] as f64);
+                let (v0, v1) = (self.values[index - 1], self.values[index]);
+                Some(v0 + (v1 - v0) * (epoch as f64 - e0) / (e1 - e0))
+            }
+        }
+    }
+
+    /// Sample both series at common epochs
+    pub fn align(&self, other: &MetricSeries, alignment: Alignment) -> AlignedSeries {
+        let epochs: Vec<usize> = match alignment {
+            Alignment::Truncate => self
+                .epochs
+                .iter()
+                .copied()
+                .filter(|epoch| other.epochs.binary_search(epoch).is_ok())
+                .collect(),
+            Alignment::Interpolate => {
+                let mut union: Vec<usize> = self.epochs.iter().chain(&other.epochs).copied().collect();
+                union.sort_unstable();
+                union.dedup();
+                union
+                    .into_iter()
+                    .filter(|&epoch| self.value_at(epoch).is_some() && other.value_at(epoch).is_some())
+                    .collect()
+            }
+        };
+
+        AlignedSeries {
+            left: epochs.iter().filter_map(|&epoch| self.value_at(epoch)).collect(),
+            right: epochs.iter().filter_map(|&epoch| other.value_at(epoch)).collect(),
+            epochs,
+        }
+    }
+
+    /// `self - other` at the aligned epochs
+    pub fn difference(&self, other: &MetricSeries, alignment: Alignment) -> MetricSeries {
+        self.combine(other, alignment, "-", |a, b| a - b)
+    }
+
+    /// `self / other` at the aligned epochs
+    pub fn ratio(&self, other: &MetricSeries, alignment: Alignment) -> MetricSeries {
+        self.combine(other, alignment, "/", |a, b| a / b)
+    }
+
+    fn combine(&self, other: &MetricSeries, alignment: Alignment, op: &str, f: impl Fn(f64, f64) -> f64) -> MetricSeries {
+        let aligned = self.align(other, alignment);
+
+        MetricSeries {
+            name: format!("{} {} {}", self.name, op, other.name),
+            values: aligned.left.iter().zip(&aligned.right).map(|(&a, &b)| f(a, b)).collect(),
+            epochs: aligned.epochs,
+        }
+    }
+}
@@ -0,0 +1,302 @@
+#![deny(clippy::indexing_slicing)]
+
+use std::cmp::Reverse;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::network::{EDNetworkBuilder, LayerType};
+use super::training::TrainingPattern;
+use super::utils::{Activation, OutputActivation};
+
+/// How urgently a finding should be addressed
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+pub enum Severity {
+    /// Worth knowing, learning will still work
+    Info,
+    /// Learning will likely be slow or fail to converge
+    Warning,
+    /// Training cannot run or cannot succeed
+    Error,
+}
+
+/// Machine-readable identifier of a preflight finding
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FindingCode {
+    /// Dataset contains no patterns
+    EmptyDataset,
+    /// Pattern input length differs from the builder's input size
+    InputSizeMismatch,
+    /// Pattern target length differs from the builder's output size
+    TargetSizeMismatch,
+    /// Pattern contains NaN or infinity
+    NonFiniteValue,
+    /// Target outside the range the configured output activation can reach
+    TargetOutOfRange,
+    /// Input outside [0, 1], saturating the sigmoid neurons
+    InputOutOfRange,
+    /// Identical inputs mapped to different targets
+    ConflictingPatterns,
+    /// Hidden layer too small for the input width
+    HiddenTooSmall,
+    /// Total-error convergence threshold unreachable for the dataset size
+    ConvergenceUnreachable,
+    /// Input column with the same value in every pattern
+    ConstantInput,
+}
+
+/// One problem detected before training, with a suggested fix
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Finding {
+    pub severity: Severity,
+    pub code: FindingCode,
+    /// What was detected
+    pub message: String,
+    /// Concrete change to the config or dataset that resolves it
+    pub suggestion: String,
+}
+
+/// Findings of a preflight check, most severe first
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PreflightReport {
+    pub findings: Vec<Finding>,
+}
+
+impl PreflightReport {
+    /// Whether nothing was found
+    pub fn is_clean(&self) -> bool {
+        self.findings.is_empty()
+    }
+
+    /// Whether any finding prevents training from succeeding
+    pub fn has_errors(&self) -> bool {
+        self.findings.iter().any(|finding| finding.severity == Severity::Error)
+    }
+
+    /// Codes of all findings in report order
+    pub fn codes(&self) -> Vec<FindingCode> {
+        self.findings.iter().map(|finding| finding.code).collect()
+    }
+
+    fn push(&mut self, severity: Severity, code: FindingCode, message: String, suggestion: String) {
+        self.findings.push(Finding {
+            severity,
+            code,
+            message,
+            suggestion,
+        });
+    }
+}
+
+impl fmt::Display for PreflightReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.findings.is_empty() {
+            return write!(f, "preflight: no findings");
+        }
+
+        for finding in &self.findings {
+            writeln!(
+                f,
+                "[{:?}] {:?}: {} (fix: {})",
+                finding.severity, finding.code, finding.message, finding.suggestion
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Check a network builder against a dataset before training
+///
+/// Runs every dataset/config validator and returns the findings sorted by severity.
+pub fn preflight(builder: &EDNetworkBuilder, dataset: &[TrainingPattern]) -> PreflightReport {
+    let mut report = PreflightReport::default();
+
+    if dataset.is_empty() {
+        report.push(
+            Severity::Error,
+            FindingCode::EmptyDataset,
+            "dataset contains no patterns".into(),
+            "provide at least one TrainingPattern".into(),
+        );
+        return report;
+    }
+
+    check_shapes(builder, dataset, &mut report);
+    check_ranges(builder, dataset, &mut report);
+    check_conflicts(dataset, &mut report);
+    check_constant_inputs(builder, dataset, &mut report);
+    check_hidden_size(builder, &mut report);
+    check_convergence_metric(builder, dataset, &mut report);
+
+    // Stable sort keeps detection order within a severity
+    report.findings.sort_by_key(|finding| Reverse(finding.severity));
+
+    report
+}
+
+fn check_shapes(builder: &EDNetworkBuilder, dataset: &[TrainingPattern], report: &mut PreflightReport) {
+    if let Some(pattern) = dataset.iter().find(|pattern| pattern.inputs.len() != builder.input_size) {
+        report.push(
+            Severity::Error,
+            FindingCode::InputSizeMismatch,
+            format!(
+                "pattern {} has {} inputs, network expects {}",
+                pattern.id,
+                pattern.inputs.len(),
+                builder.input_size
+            ),
+            format!("set .inputs({}) or fix the dataset", pattern.inputs.len()),
+        );
+    }
+
+    if let Some(pattern) = dataset.iter().find(|pattern| pattern.targets.len() != builder.output_size) {
+        report.push(
+            Severity::Error,
+            FindingCode::TargetSizeMismatch,
+            format!(
+                "pattern {} has {} targets, network expects {}",
+                pattern.id,
+                pattern.targets.len(),
+                builder.output_size
+            ),
+            format!("set .outputs({}) or fix the dataset", pattern.targets.len()),
+        );
+    }
+}
+
+/// Closed range the output layer can produce, with the activation's name for messages
+fn output_range(builder: &EDNetworkBuilder) -> (f64, f64, &'static str) {
+    if builder.config.output_layer == OutputActivation::Softmax {
+        return (0.0, 1.0, "softmax");
+    }
+
+    match builder.config.activation_for(LayerType::Output) {
+        Activation::Sigmoid => (0.0, 1.0, "sigmoid"),
+        Activation::Tanh => (-1.0, 1.0, "tanh"),
+        Activation::ReLU => (0.0, f64::INFINITY, "ReLU"),
+        Activation::LeakyReLU(_) => (f64::NEG_INFINITY, f64::INFINITY, "leaky ReLU"),
+        Activation::Linear => (f64::NEG_INFINITY, f64::INFINITY, "linear"),
+    }
+}
+
+fn check_ranges(builder: &EDNetworkBuilder, dataset: &[TrainingPattern], report: &mut PreflightReport) {
+    let values = |pattern: &TrainingPattern| pattern.inputs.iter().chain(&pattern.targets).all(|value| value.is_finite());
+    if let Some(pattern) = dataset.iter().find(|pattern| !values(pattern)) {
+        report.push(
+            Severity::Error,
+            FindingCode::NonFiniteValue,
+            format!("pattern {} contains NaN or infinity", pattern.id),
+            "remove or impute the non-finite values".into(),
+        );
+    }
+
+    let (low, high, name) = output_range(builder);
+    let unreachable = |value: &f64| *value < low || *value > high;
+
+    if let Some(pattern) = dataset.iter().find(|pattern| pattern.targets.iter().any(unreachable)) {
+        report.push(
+            Severity::Error,
+            FindingCode::TargetOutOfRange,
+            format!(
+                "pattern {} has targets outside [{}, {}], unreachable by the {} output",
+                pattern.id, low, high, name
+            ),
+            format!("rescale targets into [{}, {}]", low, high),
+        );
+    }
+
+    let outside = |value: &f64| *value < 0.0 || *value > 1.0;
+
+    if let Some(pattern) = dataset.iter().find(|pattern| pattern.inputs.iter().any(outside)) {
+        report.push(
+            Severity::Warning,
+            FindingCode::InputOutOfRange,
+            format!(
+                "pattern {} has inputs outside [0, 1], which saturates the sigmoid neurons",
+                pattern.id
+            ),
+            "min-max normalize every input column into [0, 1]".into(),
+        );
+    }
+}
+
+fn check_conflicts(dataset: &[TrainingPattern], report: &mut PreflightReport) {
+    for (index, pattern) in dataset.iter().enumerate() {
+        let conflict = dataset
+            .iter()
+            .skip(index + 1)
+            .find(|other| other.inputs == pattern.inputs && other.targets != pattern.targets);
+
+        if let Some(other) = conflict {
+            report.push(
+                Severity::Error,
+                FindingCode::ConflictingPatterns,
+                format!("patterns {} and {} share inputs but have different targets", pattern.id, other.id),
+                "remove or relabel one of the duplicates".into(),
+            );
+            return;
+        }
+    }
+}
+
+fn check_constant_inputs(builder: &EDNetworkBuilder, dataset: &[TrainingPattern], report: &mut PreflightReport) {
+    if dataset.len() < 2 {
+        return;
+    }
+
+    // Columns beyond the first pattern are already reported as a shape mismatch
+    let columns = dataset.first().map_or(0, |pattern| pattern.inputs.len()).min(builder.input_size);
+    for column in 0..columns {
+        let first = dataset.first().and_then(|pattern| pattern.inputs.get(column));
+        if dataset.iter().all(|pattern| pattern.inputs.get(column) == first) {
+            report.push(
+                Severity::Info,
+                FindingCode::ConstantInput,
+                format!(
+                    "input column {} has the same value in every pattern and carries no information",
+                    column
+                ),
+                format!("drop input column {} and reduce .inputs()", column),
+            );
+        }
+    }
+}
+
+fn check_hidden_size(builder: &EDNetworkBuilder, report: &mut PreflightReport) {
+    // Each logical input becomes an excitatory/inhibitory pair; fewer hidden units than that starves learning
+    let input_neurons = builder.input_size.saturating_mul(2);
+    let recommended = input_neurons.max(4);
+
+    if builder.hidden_size < input_neurons {
+        report.push(
+            Severity::Warning,
+            FindingCode::HiddenTooSmall,
+            format!(
+                "hidden layer has {} neurons for {} input neurons",
+                builder.hidden_size, input_neurons
+            ),
+            format!("set .hidden({}) or more", recommended),
+        );
+    }
+}
+
+fn check_convergence_metric(builder: &EDNetworkBuilder, dataset: &[TrainingPattern], report: &mut PreflightReport) {
+    // convergence_threshold bounds the summed absolute error of a whole epoch, so it shrinks per pattern as data grows
+    let slots = dataset.len().saturating_mul(builder.output_size.max(1)) as f64;
+    let per_output = builder.config.convergence_threshold / slots;
+
+    if per_output < 1e-3 {
+        report.push(
+            Severity::Warning,
+            FindingCode::ConvergenceUnreachable,
+            format!(
+                "convergence_threshold {} allows only {:.2e} error per output over {} patterns",
+                builder.config.convergence_threshold,
+                per_output,
+                dataset.len()
+            ),
+            format!("raise convergence_threshold to about {}", 0.01 * slots),
+        );
+    }
+}
@@ -0,0 +1,240 @@
+#![deny(clippy::indexing_slicing)]
+
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+use super::MAX_OUTPUT_NETWORKS;
+use super::error::EdError;
+use super::network::EDNetwork;
+use super::training::TrainingPattern;
+
+/// How validation errors are turned into member weights
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum AggregationScheme {
+    /// Softmax over `-error / temperature`; lower temperatures favour the best member harder
+    SoftmaxNegativeError { temperature: f64 },
+    /// `1 / error`, normalized to sum to one
+    InverseError,
+}
+
+impl Default for AggregationScheme {
+    fn default() -> Self {
+        AggregationScheme::SoftmaxNegativeError {
+            temperature: 0.05,
+        }
+    }
+}
+
+/// How `predict_ensemble` combines the member outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum EnsembleMode {
+    /// Mean of the member outputs, as `predict`
+    #[default]
+    Average,
+    /// 1.0 for an output when more than half of the members put it at or above 0.5, else 0.0
+    MajorityVote,
+}
+
+/// Member weights fitted on a validation set
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct AggregationWeights {
+    pub scheme: AggregationScheme,
+    /// One weight per member, summing to one
+    pub weights: Vec<f64>,
+    /// Mean absolute validation error per pattern of each member
+    pub validation_errors: Vec<f64>,
+}
+
+/// Independently trained networks sharing input and output sizes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EDEnsemble {
+    pub members: Vec<EDNetwork>,
+    /// Weights used by `predict_weighted`, uniform until fitted
+    pub aggregation: Option<AggregationWeights>,
+}
+
+impl EDEnsemble {
+    /// Group networks into an ensemble, rejecting an empty list, more than `MAX_OUTPUT_NETWORKS`
+    /// members or mismatched dimensions
+    pub fn new(members: Vec<EDNetwork>) -> Result<Self, EdError> {
+        let first = members
+            .first()
+            .ok_or_else(|| EdError::InvalidConfig("ensemble needs at least one member".into()))?;
+        if members.len() > MAX_OUTPUT_NETWORKS {
+            return Err(EdError::InvalidConfig(format!(
+                "ensemble holds at most {} members, got {}",
+                MAX_OUTPUT_NETWORKS,
+                members.len()
+            )));
+        }
+        let (inputs, outputs) = (first.dimensions.input_size, first.dimensions.output_size);
+
+        for member in &members {
+            if member.dimensions.input_size != inputs {
+                return Err(EdError::DimensionMismatch {
+                    expected: inputs,
+                    got: member.dimensions.input_size,
+                });
+            }
+            if member.dimensions.output_size != outputs {
+                return Err(EdError::DimensionMismatch {
+                    expected: outputs,
+                    got: member.dimensions.output_size,
+                });
+            }
+        }
+
+        Ok(Self {
+            members,
+            aggregation: None,
+        })
+    }
+
+    /// Plain average of the member predictions
+    pub fn predict(&self, inputs: &[f64]) -> Result<Vec<f64>, EdError> {
+        let uniform = vec![1.0 / self.members.len() as f64; self.members.len()];
+        self.combine(inputs, &uniform)
+    }
+
+    /// Member predictions combined by `mode`
+    pub fn predict_ensemble(&self, inputs: &[f64], mode: EnsembleMode) -> Result<Vec<f64>, EdError> {
+        match mode {
+            EnsembleMode::Average => self.predict(inputs),
+            EnsembleMode::MajorityVote => {
+                let mut votes = vec![0; self.output_size()?];
+                for member in &self.members {
+                    for (count, output) in votes.iter_mut().zip(member.predict(inputs)?) {
+                        if output >= 0.5 {
+                            *count += 1;
+                        }
+                    }
+                }
+
+                Ok(votes
+                    .into_iter()
+                    .map(|count| {
+                        if 2 * count > self.members.len() {
+                            1.0
+                        } else {
+                            0.0
+                        }
+                    })
+                    .collect())
+            }
+        }
+    }
+
+    /// Average of the member predictions weighted by the fitted aggregation weights
+    pub fn predict_weighted(&self, inputs: &[f64]) -> Result<Vec<f64>, EdError> {
+        match &self.aggregation {
+            Some(aggregation) => self.combine(inputs, &aggregation.weights),
+            None => self.predict(inputs),
+        }
+    }
+
+    /// Evaluate every member on `validation` and derive per-member weights with `scheme`
+    ///
+    /// Members with equal validation error always receive equal weight.
+    pub fn fit_aggregation_weights(
+        &mut self,
+        validation: &[TrainingPattern],
+        scheme: AggregationScheme,
+    ) -> Result<&AggregationWeights, EdError> {
+        if validation.is_empty() {
+            return Err(EdError::EmptyTrainingSet);
+        }
+
+        let validation_errors = self
+            .members
+            .iter()
+            .map(|member| mean_error(member, validation))
+            .collect::<Result<Vec<f64>, EdError>>()?;
+
+        let raw: Vec<f64> = match scheme {
+            AggregationScheme::SoftmaxNegativeError {
+                temperature,
+            } => {
+                if !(temperature.is_finite() && temperature > 0.0) {
+                    return Err(EdError::InvalidConfig(format!(
+                        "softmax temperature must be positive, got {}",
+                        temperature
+                    )));
+                }
+                // Shift by the best error so the largest exponent is zero
+                let best = validation_errors.iter().copied().fold(f64::INFINITY, f64::min);
+                validation_errors
+                    .iter()
+                    .map(|error| (-(error - best) / temperature).exp())
+                    .collect()
+            }
+            AggregationScheme::InverseError => validation_errors.iter().map(|error| 1.0 / error.max(f64::EPSILON)).collect(),
+        };
+        let total: f64 = raw.iter().sum();
+
+        Ok(self.aggregation.insert(AggregationWeights {
+            scheme,
+            weights: raw.iter().map(|weight| weight / total).collect(),
+            validation_errors,
+        }))
+    }
+
+    fn output_size(&self) -> Result<usize, EdError> {
+        let first = self
+            .members
+            .first()
+            .ok_or_else(|| EdError::InvalidConfig("ensemble needs at least one member".into()))?;
+        Ok(first.dimensions.output_size)
+    }
+
+    fn combine(&self, inputs: &[f64], weights: &[f64]) -> Result<Vec<f64>, EdError> {
+        let mut combined = vec![0.0; self.output_size()?];
+
+        for (member, weight) in self.members.iter().zip(weights) {
+            for (sum, output) in combined.iter_mut().zip(member.predict(inputs)?) {
+                *sum += weight * output;
+            }
+        }
+
+        Ok(combined)
+    }
+}
+
+impl fmt::Display for EDEnsemble {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "Ensemble of {} members", self.members.len())?;
+
+        match &self.aggregation {
+            Some(aggregation) => {
+                writeln!(f, "Aggregation: {:?}", aggregation.scheme)?;
+                for (i, (weight, error)) in aggregation.weights.iter().zip(&aggregation.validation_errors).enumerate() {
+                    writeln!(f, "  member {}: weight {:.4} validation error {:.6}", i, weight, error)?;
+                }
+                Ok(())
+            }
+            None => writeln!(f, "Aggregation: uniform"),
+        }
+    }
+}
+
+/// Mean summed absolute error per pattern of one network
+fn mean_error(network: &EDNetwork, patterns: &[TrainingPattern]) -> Result<f64, EdError> {
+    let mut total = 0.0;
+
+    for pattern in patterns {
+        let outputs = network.predict(&pattern.inputs)?;
+        if outputs.len() != pattern.targets.len() {
+            return Err(EdError::DimensionMismatch {
+                expected: outputs.len(),
+                got: pattern.targets.len(),
+            });
+        }
+        total += outputs
+            .iter()
+            .zip(&pattern.targets)
+            .map(|(output, target)| (target - output).abs())
+            .sum::<f64>();
+    }
+
+    Ok(total / patterns.len() as f64)
+}
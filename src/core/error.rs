@@ -0,0 +1,159 @@
+use std::error::Error;
+use std::{fmt, io};
+
+/// Errors produced by fallible ED network operations
+#[derive(Debug)]
+pub enum EdError {
+    /// Pattern or buffer length does not match the network dimensions
+    DimensionMismatch { expected: usize, got: usize },
+    /// Total neuron count exceeds `MAX_NETWORK_SIZE`
+    NetworkTooLarge { neurons: usize, max: usize },
+    /// Configuration parameter outside its meaningful range
+    InvalidConfig(String),
+    /// Connection topology cannot carry signals or error to every output
+    InvalidTopology(String),
+    /// Non-finite value produced during a forward or learning step
+    NumericalInstability(String),
+    /// Training dataset contains no patterns
+    EmptyTrainingSet,
+    /// Saved network data could not be encoded or decoded
+    SerializationError(String),
+    /// Saved network was written by an incompatible format version
+    VersionMismatch { expected: u16, found: u16 },
+    /// Reading or writing a model file failed
+    Io(io::Error),
+}
+
+impl Clone for EdError {
+    fn clone(&self) -> Self {
+        match self {
+            // io::Error is not Clone, so keep its kind and message
+            EdError::Io(err) => EdError::Io(io::Error::new(err.kind(), err.to_string())),
+            EdError::DimensionMismatch {
+                expected,
+                got,
+            } => EdError::DimensionMismatch {
+                expected: *expected,
+                got: *got,
+            },
+            EdError::NetworkTooLarge {
+                neurons,
+                max,
+            } => EdError::NetworkTooLarge {
+                neurons: *neurons,
+                max: *max,
+            },
+            EdError::InvalidConfig(msg) => EdError::InvalidConfig(msg.clone()),
+            EdError::InvalidTopology(msg) => EdError::InvalidTopology(msg.clone()),
+            EdError::NumericalInstability(msg) => EdError::NumericalInstability(msg.clone()),
+            EdError::EmptyTrainingSet => EdError::EmptyTrainingSet,
+            EdError::SerializationError(msg) => EdError::SerializationError(msg.clone()),
+            EdError::VersionMismatch {
+                expected,
+                found,
+            } => EdError::VersionMismatch {
+                expected: *expected,
+                found: *found,
+            },
+        }
+    }
+}
+
+impl PartialEq for EdError {
+    /// I/O errors compare by kind, everything else structurally
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (EdError::Io(a), EdError::Io(b)) => a.kind() == b.kind(),
+            (
+                EdError::DimensionMismatch {
+                    expected: a,
+                    got: b,
+                },
+                EdError::DimensionMismatch {
+                    expected: c,
+                    got: d,
+                },
+            ) => (a, b) == (c, d),
+            (
+                EdError::NetworkTooLarge {
+                    neurons: a,
+                    max: b,
+                },
+                EdError::NetworkTooLarge {
+                    neurons: c,
+                    max: d,
+                },
+            ) => (a, b) == (c, d),
+            (EdError::InvalidConfig(a), EdError::InvalidConfig(b))
+            | (EdError::InvalidTopology(a), EdError::InvalidTopology(b))
+            | (EdError::NumericalInstability(a), EdError::NumericalInstability(b))
+            | (EdError::SerializationError(a), EdError::SerializationError(b)) => a == b,
+            (EdError::EmptyTrainingSet, EdError::EmptyTrainingSet) => true,
+            (
+                EdError::VersionMismatch {
+                    expected: a,
+                    found: b,
+                },
+                EdError::VersionMismatch {
+                    expected: c,
+                    found: d,
+                },
+            ) => (a, b) == (c, d),
+            _ => false,
+        }
+    }
+}
+
+impl fmt::Display for EdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EdError::DimensionMismatch {
+                expected,
+                got,
+            } => {
+                write!(f, "dimension mismatch: expected {}, got {}", expected, got)
+            }
+            EdError::NetworkTooLarge {
+                neurons,
+                max,
+            } => write!(f, "network has {} neurons, maximum is {}", neurons, max),
+            EdError::InvalidConfig(msg) => write!(f, "invalid config: {}", msg),
+            EdError::InvalidTopology(msg) => write!(f, "invalid topology: {}", msg),
+            EdError::NumericalInstability(msg) => write!(f, "numerical instability: {}", msg),
+            EdError::EmptyTrainingSet => write!(f, "training set is empty"),
+            EdError::SerializationError(msg) => write!(f, "serialization error: {}", msg),
+            EdError::VersionMismatch {
+                expected,
+                found,
+            } => write!(f, "format version {} is not supported, expected {}", found, expected),
+            EdError::Io(err) => write!(f, "I/O error: {}", err),
+        }
+    }
+}
+
+impl Error for EdError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EdError::Io(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for EdError {
+    fn from(err: io::Error) -> Self {
+        EdError::Io(err)
+    }
+}
+
+impl serde::ser::Error for EdError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        EdError::SerializationError(msg.to_string())
+    }
+}
+
+impl serde::de::Error for EdError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        EdError::SerializationError(msg.to_string())
+    }
+}
@@ -0,0 +1,274 @@
+use serde::{Deserialize, Serialize};
+
+use super::MAX_NETWORK_SIZE;
+use super::error::EdError;
+use super::network::{EDNetwork, LayerEiRatios, NetworkDimensions};
+use super::neuron::NeuronType;
+
+/// Where the two bias neurons sit in the index space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BiasPosition {
+    /// Bias pair occupies indices 0 and 1
+    First,
+    /// Bias pair occupies the last two indices
+    Last,
+}
+
+/// Order of the two neurons making up a bias or doubled input pair
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PairOrder {
+    /// Inhibitory twin first, then excitatory (this crate)
+    InhibitoryFirst,
+    /// Excitatory twin first, then inhibitory (Kaneko's C code)
+    ExcitatoryFirst,
+}
+
+/// Relative order of the hidden and output blocks
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum OutputPosition {
+    /// Output neurons follow the hidden layer
+    AfterHidden,
+    /// Output neurons precede the hidden layer
+    BeforeHidden,
+}
+
+/// Which neuron selects the row of a flat weight matrix
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MatrixOrientation {
+    /// `weights[from * n + to]`
+    FromTo,
+    /// `weights[to * n + from]`
+    ToFrom,
+}
+
+/// Start index of each layer block within a convention's index space
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LayerOffsets {
+    pub bias: usize,
+    pub input: usize,
+    pub hidden: usize,
+    pub output: usize,
+}
+
+/// Descriptor of how a flat weight matrix lays out the neurons of an ED network
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct IndexingConvention {
+    /// Network dimensions the matrix was written for
+    pub dimensions: NetworkDimensions,
+    /// Placement of the bias pair
+    pub bias_position: BiasPosition,
+    /// Ordering inside bias and doubled input pairs
+    pub pair_order: PairOrder,
+    /// Placement of the output block relative to the hidden block
+    pub output_position: OutputPosition,
+    /// Row/column meaning of the flat matrix
+    pub orientation: MatrixOrientation,
+    /// Whether stored weights already carry the neuron-type sign applied by `Connection::new`
+    pub signed_weights: bool,
+}
+
+impl IndexingConvention {
+    /// Layout used by `EDNetwork::connections`
+    pub fn edla_rs(dimensions: NetworkDimensions) -> Self {
+        Self {
+            dimensions,
+            bias_position: BiasPosition::First,
+            pair_order: PairOrder::InhibitoryFirst,
+            output_position: OutputPosition::AfterHidden,
+            orientation: MatrixOrientation::FromTo,
+            signed_weights: true,
+        }
+    }
+
+    /// Layout of the original EDLA C reference (`weights[target][source]`, output at index `in + 2`)
+    pub fn kaneko_c(dimensions: NetworkDimensions) -> Self {
+        Self {
+            dimensions,
+            bias_position: BiasPosition::First,
+            pair_order: PairOrder::ExcitatoryFirst,
+            output_position: OutputPosition::BeforeHidden,
+            orientation: MatrixOrientation::ToFrom,
+            signed_weights: true,
+        }
+    }
+
+    /// Start index of each layer block
+    pub fn offsets(&self) -> LayerOffsets {
+        let dims = &self.dimensions;
+        let bias = match self.bias_position {
+            BiasPosition::First => 0,
+            BiasPosition::Last => dims.total_neurons.saturating_sub(2),
+        };
+        let input = match self.bias_position {
+            BiasPosition::First => 2,
+            BiasPosition::Last => 0,
+        };
+        let inputs_end = dims.input_neurons().saturating_add(input);
+        let (hidden, output) = match self.output_position {
+            OutputPosition::AfterHidden => (inputs_end, inputs_end.saturating_add(dims.hidden_size)),
+            OutputPosition::BeforeHidden => (inputs_end.saturating_add(dims.output_size), inputs_end),
+        };
+
+        LayerOffsets {
+            bias,
+            input,
+            hidden,
+            output,
+        }
+    }
+
+    /// Physical index of every neuron, listed in this crate's global order
+    fn physical_indices(&self) -> Vec<usize> {
+        let dims = &self.dimensions;
+        let offsets = self.offsets();
+        let mut indices = Vec::with_capacity(dims.total_neurons);
+
+        // Crate order inside a pair is (inhibitory, excitatory)
+        let pair = |start: usize| match self.pair_order {
+            PairOrder::InhibitoryFirst => [start, start + 1],
+            PairOrder::ExcitatoryFirst => [start + 1, start],
+        };
+
+        indices.extend(pair(offsets.bias));
+        if dims.inhibitory_inputs {
+            for input in 0..dims.input_size {
+                indices.extend(pair(offsets.input + input * 2));
+            }
+        } else {
+            indices.extend(offsets.input..offsets.input + dims.input_size);
+        }
+        indices.extend(offsets.hidden..offsets.hidden + dims.hidden_size);
+        indices.extend(offsets.output..offsets.output + dims.output_size);
+
+        indices
+    }
+
+    /// Position of a connection inside the flat matrix
+    fn flat_index(&self, from: usize, to: usize) -> usize {
+        let n = self.dimensions.total_neurons;
+
+        match self.orientation {
+            MatrixOrientation::FromTo => from * n + to,
+            MatrixOrientation::ToFrom => to * n + from,
+        }
+    }
+}
+
+/// Neuron types in this crate's global order
+fn neuron_types(dimensions: &NetworkDimensions) -> Vec<NeuronType> {
+    let mut types = vec![NeuronType::Inhibitory, NeuronType::Excitatory];
+
+    if dimensions.inhibitory_inputs {
+        types.extend((0..dimensions.input_size * 2).map(NeuronType::from_index));
+    } else {
+        types.extend((0..dimensions.input_size).map(|_| NeuronType::Excitatory));
+    }
+    types.extend((0..dimensions.hidden_size).map(NeuronType::from_index));
+    types.extend((0..dimensions.output_size).map(|_| NeuronType::Excitatory));
+
+    types
+}
+
+/// Permute (and negate where needed) a flat weight matrix from one indexing convention to another
+pub fn remap(weights: &[f64], from: &IndexingConvention, to: &IndexingConvention) -> Result<Vec<f64>, EdError> {
+    if from.dimensions != to.dimensions {
+        return Err(EdError::DimensionMismatch {
+            expected: to.dimensions.total_neurons,
+            got: from.dimensions.total_neurons,
+        });
+    }
+
+    let dims = &from.dimensions;
+    let n = dims.total_neurons;
+    let expected =
+        NetworkDimensions::new(dims.input_size, dims.hidden_size, dims.output_size).with_inhibitory_inputs(dims.inhibitory_inputs);
+    if n != expected.total_neurons {
+        return Err(EdError::InvalidTopology(format!(
+            "total_neurons {} does not match the layer sizes",
+            n
+        )));
+    }
+    if n > MAX_NETWORK_SIZE {
+        return Err(EdError::NetworkTooLarge {
+            neurons: n,
+            max: MAX_NETWORK_SIZE,
+        });
+    }
+    if weights.len() != n * n {
+        return Err(EdError::DimensionMismatch {
+            expected: n * n,
+            got: weights.len(),
+        });
+    }
+
+    let types = neuron_types(&from.dimensions);
+    let source = from.physical_indices();
+    let target = to.physical_indices();
+    let mut remapped = vec![0.0; n * n];
+
+    for i in 0..n {
+        for j in 0..n {
+            let mut weight = weights[from.flat_index(source[i], source[j])];
+
+            if from.signed_weights != to.signed_weights {
+                weight *= types[i].as_weight_factor() * types[j].as_weight_factor();
+            }
+
+            remapped[to.flat_index(target[i], target[j])] = weight;
+        }
+    }
+
+    Ok(remapped)
+}
+
+impl EDNetwork {
+    /// Indexing convention describing `self.connections`
+    pub fn indexing_convention(&self) -> IndexingConvention {
+        IndexingConvention::edla_rs(self.dimensions.clone())
+    }
+
+    /// Flatten the connection weights into another convention's layout
+    pub fn export_weights(&self, to: &IndexingConvention) -> Result<Vec<f64>, EdError> {
+        self.check_sign_conversion(to)?;
+        let weights: Vec<f64> = self.connections.iter().flatten().map(|connection| connection.weight).collect();
+
+        remap(&weights, &self.indexing_convention(), to)
+    }
+
+    /// Replace the connection weights with a flat matrix stored in another convention's layout
+    ///
+    /// Zero weights mark absent connections, as in the C reference, so they are disabled on import.
+    pub fn import_weights(&mut self, weights: &[f64], from: &IndexingConvention) -> Result<(), EdError> {
+        self.check_invariants()?;
+        self.check_sign_conversion(from)?;
+        if let Some(index) = weights.iter().position(|weight| !weight.is_finite()) {
+            return Err(EdError::NumericalInstability(format!(
+                "weight {} at flat index {} is not finite",
+                weights[index], index
+            )));
+        }
+
+        let remapped = remap(weights, from, &self.indexing_convention())?;
+        let n = self.dimensions.total_neurons;
+
+        for (from, row) in self.connections.iter_mut().enumerate() {
+            for (to, connection) in row.iter_mut().enumerate() {
+                connection.weight = remapped[from * n + to];
+                connection.connection_enabled = connection.weight != 0.0;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Sign conversion assumes the alternating neuron types, which custom E/I ratios replace
+    fn check_sign_conversion(&self, other: &IndexingConvention) -> Result<(), EdError> {
+        if self.config.ei_ratios != LayerEiRatios::default() && other.signed_weights != self.indexing_convention().signed_weights {
+            return Err(EdError::InvalidTopology(
+                "cannot convert weight signs for a network with custom ei_ratios".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,337 @@
+//! Minimal gzip (RFC 1952) reader over a DEFLATE (RFC 1951) decoder, for compressed dataset files
+//!
+//! Decoding only; every malformed stream is reported as an `EdError::SerializationError`.
+
+use super::error::EdError;
+
+/// Decompress every member of a gzip file, checking each trailer's CRC-32 and size
+pub(crate) fn decompress(bytes: &[u8]) -> Result<Vec<u8>, EdError> {
+    let mut output = Vec::new();
+    let mut rest = bytes;
+
+    while !rest.is_empty() {
+        let consumed = member(rest, &mut output)?;
+        rest = rest.get(consumed..).unwrap_or_default();
+    }
+
+    Ok(output)
+}
+
+fn invalid(msg: &str) -> EdError {
+    EdError::SerializationError(format!("gzip: {}", msg))
+}
+
+const FLAG_HCRC: u8 = 0x02;
+const FLAG_EXTRA: u8 = 0x04;
+const FLAG_NAME: u8 = 0x08;
+const FLAG_COMMENT: u8 = 0x10;
+
+/// Decode one member onto `output`, returning the bytes it occupied
+fn member(bytes: &[u8], output: &mut Vec<u8>) -> Result<usize, EdError> {
+    let header = bytes.get(..10).ok_or_else(|| invalid("truncated header"))?;
+    if header[..2] != [0x1f, 0x8b] || header[2] != 8 {
+        return Err(invalid("not a deflate-compressed gzip member"));
+    }
+    let flags = header[3];
+    let mut pos = 10;
+
+    if flags & FLAG_EXTRA != 0 {
+        let length = bytes.get(pos..pos + 2).ok_or_else(|| invalid("truncated extra field"))?;
+        pos += 2 + usize::from(u16::from_le_bytes([length[0], length[1]]));
+    }
+    for flag in [FLAG_NAME, FLAG_COMMENT] {
+        if flags & flag != 0 {
+            let end = bytes.get(pos..).and_then(|tail| tail.iter().position(|&byte| byte == 0));
+            pos += end.ok_or_else(|| invalid("unterminated header string"))? + 1;
+        }
+    }
+    if flags & FLAG_HCRC != 0 {
+        pos += 2;
+    }
+
+    let start = output.len();
+    let mut bits = Bits::new(bytes.get(pos..).ok_or_else(|| invalid("truncated header"))?);
+    inflate(&mut bits, output, start)?;
+    pos += bits.pos;
+
+    let trailer = bytes.get(pos..pos + 8).ok_or_else(|| invalid("truncated trailer"))?;
+    let crc = u32::from_le_bytes([trailer[0], trailer[1], trailer[2], trailer[3]]);
+    let size = u32::from_le_bytes([trailer[4], trailer[5], trailer[6], trailer[7]]);
+    let data = output.get(start..).unwrap_or_default();
+    if crc32(data) != crc || data.len() as u32 != size {
+        return Err(invalid("checksum or size mismatch"));
+    }
+
+    Ok(pos + 8)
+}
+
+/// CRC-32 (IEEE, reflected) lookup table
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0u32; 256];
+    let mut n = 0;
+    while n < 256 {
+        let mut c = n as u32;
+        let mut k = 0;
+        while k < 8 {
+            c = if c & 1 != 0 {
+                0xedb8_8320 ^ (c >> 1)
+            } else {
+                c >> 1
+            };
+            k += 1;
+        }
+        table[n] = c;
+        n += 1;
+    }
+    table
+};
+
+fn crc32(data: &[u8]) -> u32 {
+    !data.iter().fold(!0u32, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xff) as usize] ^ (crc >> 8)
+    })
+}
+
+/// LSB-first bit reader; `pos` is the next unread byte
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+    buffer: u32,
+    count: u32,
+}
+
+impl<'a> Bits<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self {
+            data,
+            pos: 0,
+            buffer: 0,
+            count: 0,
+        }
+    }
+
+    /// Next `n` (at most 16) bits as an integer
+    fn take(&mut self, n: u32) -> Result<u32, EdError> {
+        while self.count < n {
+            let byte = *self.data.get(self.pos).ok_or_else(|| invalid("truncated deflate stream"))?;
+            self.pos += 1;
+            self.buffer |= u32::from(byte) << self.count;
+            self.count += 8;
+        }
+
+        let value = self.buffer & ((1 << n) - 1);
+        self.buffer >>= n;
+        self.count -= n;
+        Ok(value)
+    }
+
+    /// Drop the bits left in the current byte; fewer than 8 are ever buffered
+    fn align(&mut self) {
+        self.buffer = 0;
+        self.count = 0;
+    }
+}
+
+const MAX_BITS: usize = 15;
+
+/// Canonical Huffman code, decoded one bit at a time
+struct Huffman {
+    counts: [u16; MAX_BITS + 1],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Result<Self, EdError> {
+        let mut counts = [0u16; MAX_BITS + 1];
+        for &length in lengths {
+            *counts.get_mut(usize::from(length)).ok_or_else(|| invalid("code length over 15"))? += 1;
+        }
+        counts[0] = 0;
+
+        let mut left = 1i32;
+        for &count in &counts[1..] {
+            left = (left << 1) - i32::from(count);
+            if left < 0 {
+                return Err(invalid("over-subscribed Huffman code"));
+            }
+        }
+
+        let mut offsets = [0u16; MAX_BITS + 2];
+        for length in 1..=MAX_BITS {
+            offsets[length + 1] = offsets[length] + counts[length];
+        }
+        let mut symbols = vec![0; lengths.len()];
+        for (symbol, &length) in lengths.iter().enumerate().filter(|(_, length)| **length != 0) {
+            let slot = &mut offsets[usize::from(length)];
+            symbols[usize::from(*slot)] = symbol as u16;
+            *slot += 1;
+        }
+
+        Ok(Self {
+            counts,
+            symbols,
+        })
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16, EdError> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+
+        for &count in &self.counts[1..] {
+            code |= bits.take(1)? as i32;
+            let count = i32::from(count);
+            if code - first < count {
+                return self
+                    .symbols
+                    .get((index + code - first) as usize)
+                    .copied()
+                    .ok_or_else(|| invalid("bad Huffman code"));
+            }
+            index += count;
+            first = (first + count) << 1;
+            code <<= 1;
+        }
+
+        Err(invalid("incomplete Huffman code"))
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131, 163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DISTANCE_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537, 2049, 3073, 4097, 6145, 8193, 12289, 16385,
+    24577,
+];
+const DISTANCE_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13, 13,
+];
+/// Order in which dynamic blocks list the code-length code lengths
+const CODE_LENGTH_ORDER: [usize; 19] = [16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15];
+
+/// Decode a raw DEFLATE stream up to and including its final block
+///
+/// Back-references may not reach before `start`, where this member's output begins.
+fn inflate(bits: &mut Bits, output: &mut Vec<u8>, start: usize) -> Result<(), EdError> {
+    loop {
+        let last = bits.take(1)? == 1;
+        match bits.take(2)? {
+            0 => stored(bits, output)?,
+            1 => {
+                let (lengths, distances) = fixed_codes()?;
+                codes(bits, output, start, &lengths, &distances)?;
+            }
+            2 => {
+                let (lengths, distances) = dynamic_codes(bits)?;
+                codes(bits, output, start, &lengths, &distances)?;
+            }
+            _ => return Err(invalid("reserved block type")),
+        }
+        if last {
+            return Ok(());
+        }
+    }
+}
+
+fn stored(bits: &mut Bits, output: &mut Vec<u8>) -> Result<(), EdError> {
+    bits.align();
+    let header = bits
+        .data
+        .get(bits.pos..bits.pos + 4)
+        .ok_or_else(|| invalid("truncated stored block"))?;
+    let length = usize::from(u16::from_le_bytes([header[0], header[1]]));
+    if u16::from_le_bytes([header[2], header[3]]) != !(length as u16) {
+        return Err(invalid("stored block length check failed"));
+    }
+    bits.pos += 4;
+
+    let block = bits
+        .data
+        .get(bits.pos..bits.pos + length)
+        .ok_or_else(|| invalid("truncated stored block"))?;
+    output.extend_from_slice(block);
+    bits.pos += length;
+    Ok(())
+}
+
+fn fixed_codes() -> Result<(Huffman, Huffman), EdError> {
+    let mut lengths = [8u8; 288];
+    lengths[144..256].fill(9);
+    lengths[256..280].fill(7);
+
+    Ok((Huffman::new(&lengths)?, Huffman::new(&[5; 30])?))
+}
+
+fn dynamic_codes(bits: &mut Bits) -> Result<(Huffman, Huffman), EdError> {
+    let literal_count = bits.take(5)? as usize + 257;
+    let distance_count = bits.take(5)? as usize + 1;
+    let code_length_count = bits.take(4)? as usize + 4;
+    if literal_count > 286 || distance_count > 30 {
+        return Err(invalid("too many length or distance codes"));
+    }
+
+    let mut code_lengths = [0u8; 19];
+    for &symbol in &CODE_LENGTH_ORDER[..code_length_count] {
+        code_lengths[symbol] = bits.take(3)? as u8;
+    }
+    let code_length_code = Huffman::new(&code_lengths)?;
+
+    let total = literal_count + distance_count;
+    let mut lengths = Vec::with_capacity(total);
+    while lengths.len() < total {
+        let symbol = code_length_code.decode(bits)?;
+        let (length, repeat) = match symbol {
+            0..=15 => (symbol as u8, 1),
+            16 => (
+                *lengths.last().ok_or_else(|| invalid("repeat with no previous length"))?,
+                3 + bits.take(2)?,
+            ),
+            17 => (0, 3 + bits.take(3)?),
+            _ => (0, 11 + bits.take(7)?),
+        };
+        if lengths.len() + repeat as usize > total {
+            return Err(invalid("code lengths overrun"));
+        }
+        lengths.extend(std::iter::repeat_n(length, repeat as usize));
+    }
+    if lengths[256] == 0 {
+        return Err(invalid("missing end-of-block code"));
+    }
+
+    Ok((Huffman::new(&lengths[..literal_count])?, Huffman::new(&lengths[literal_count..])?))
+}
+
+/// Decode literals and back-references until the end-of-block symbol
+fn codes(bits: &mut Bits, output: &mut Vec<u8>, start: usize, lengths: &Huffman, distances: &Huffman) -> Result<(), EdError> {
+    loop {
+        let symbol = usize::from(lengths.decode(bits)?);
+        match symbol {
+            0..=255 => output.push(symbol as u8),
+            256 => return Ok(()),
+            _ => {
+                let index = symbol - 257;
+                let (&base, &extra) = LENGTH_BASE
+                    .get(index)
+                    .zip(LENGTH_EXTRA.get(index))
+                    .ok_or_else(|| invalid("bad length symbol"))?;
+                let length = usize::from(base) + bits.take(u32::from(extra))? as usize;
+
+                let index = usize::from(distances.decode(bits)?);
+                let (&base, &extra) = DISTANCE_BASE
+                    .get(index)
+                    .zip(DISTANCE_EXTRA.get(index))
+                    .ok_or_else(|| invalid("bad distance symbol"))?;
+                let distance = usize::from(base) + bits.take(u32::from(extra))? as usize;
+                if distance > output.len() - start {
+                    return Err(invalid("distance before start of member"));
+                }
+
+                for _ in 0..length {
+                    output.push(output[output.len() - distance]);
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use std::fmt;
+
+use super::network::LayerType;
+use super::neuron::NeuronType;
+
+/// Everything a hook may inspect about one proposed ED weight update
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct UpdateContext {
+    /// Source neuron index
+    pub from: usize,
+    /// Target neuron index
+    pub to: usize,
+    /// Weight change the ED rule would apply
+    pub proposed_delta: f64,
+    /// Connection weight before the update
+    pub weight: f64,
+    /// Source neuron type
+    pub from_type: NeuronType,
+    /// Target neuron type
+    pub to_type: NeuronType,
+    /// Layer of the source neuron
+    pub from_layer: LayerType,
+    /// Layer of the target neuron
+    pub to_layer: LayerType,
+    /// Error channel value driving the update
+    pub error_signal: f64,
+    /// Number of completed training epochs
+    pub epoch: usize,
+}
+
+/// Research extension point for rescaling or vetoing individual ED weight updates
+///
+/// Return the delta that should actually be applied: `ctx.proposed_delta` keeps the ED rule unchanged,
+/// `0.0` vetoes the update.
+pub trait ConnectionUpdateHook: HookClone + Send + Sync {
+    fn adjust_delta(&mut self, ctx: UpdateContext) -> f64;
+}
+
+/// Cloning support so networks holding a hook stay `Clone`; implemented for every `Clone` hook
+pub trait HookClone {
+    fn clone_hook(&self) -> Box<dyn ConnectionUpdateHook>;
+}
+
+impl<T: ConnectionUpdateHook + Clone + 'static> HookClone for T {
+    fn clone_hook(&self) -> Box<dyn ConnectionUpdateHook> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn ConnectionUpdateHook> {
+    fn clone(&self) -> Self {
+        self.clone_hook()
+    }
+}
+
+impl fmt::Debug for dyn ConnectionUpdateHook {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ConnectionUpdateHook")
+    }
+}
@@ -1,7 +1,38 @@
+pub mod analysis;
+#[cfg(feature = "binary")]
+pub mod binary;
+pub mod diagnostics;
+pub mod ensemble;
+pub mod error;
+pub mod export;
+#[cfg(feature = "gzip")]
+mod gzip;
+pub mod hook;
+pub mod json;
 pub mod network;
 pub mod neuron;
+pub mod session;
 pub mod training;
 pub mod utils;
 
 pub const MAX_NETWORK_SIZE: usize = 1000;
+
+/// Public functions that panic by design, with the condition that triggers each
+///
+/// Everything else returns an `EdError` or a valid value for any input, including networks
+/// whose public fields were edited into an inconsistent layout. Panics here mirror slice
+/// indexing: the caller passes an index the type already tells it the bound of.
+pub const INTENTIONAL_PANICS: &[(&str, &str)] = &[
+    ("analysis::OrderRecorder::order", "record slot not below epochs().len()"),
+    (
+        "analysis::PatternCurveRecorder::curve",
+        "pattern not below the recorder's pattern count",
+    ),
+    (
+        "analysis::PatternCurveRecorder::solved_epoch",
+        "pattern not below the recorder's pattern count",
+    ),
+    ("training::TrainingPattern::create_parity_dataset", "n_bits of usize::BITS or more"),
+];
+/// Most members an `ensemble::EDEnsemble` may hold
 pub const MAX_OUTPUT_NETWORKS: usize = 10;
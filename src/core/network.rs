@@ -1,9 +1,38 @@
+use std::collections::BTreeMap;
 use std::fmt;
+use std::io;
+use std::ops::Range;
+use std::path::Path;
 
+use rand::rngs::{StdRng, Xoshiro256PlusPlus};
+use rand::seq::SliceRandom;
+use rand::{Rng, RngExt, SeedableRng};
 use serde::{Deserialize, Serialize};
 
-use super::neuron::{Connection, Neuron, NeuronType};
+use super::MAX_NETWORK_SIZE;
+#[cfg(feature = "binary")]
+use super::binary;
+use super::error::EdError;
+use super::hook::{ConnectionUpdateHook, UpdateContext};
+use super::json;
+use super::neuron::{Connection, ErrorChannels, ErrorSplit, Neuron, NeuronOutputMode, NeuronType};
+use super::session::{SessionCursor, StopReason, StoppingCriteria};
 use super::training::TrainingPattern;
+use super::utils::{
+    Activation, Loss, OutputActivation, WeightInit, argmax, random_gaussian, random_weight, random_weight_symmetric, sigmoid_derivative,
+    softmax,
+};
+
+/// Leading bytes of every binary model file
+#[cfg(feature = "binary")]
+pub const BINARY_MAGIC: [u8; 4] = *b"EDLA";
+
+/// Binary model format written by `save_bin`, bumped on any layout change
+#[cfg(feature = "binary")]
+pub const BINARY_FORMAT_VERSION: u16 = 34;
+
+/// Absolute output error above which a pattern counts as wrong (as in Kaneko's reference)
+pub(crate) const ERROR_COUNT_THRESHOLD: f64 = 0.5;
 
 /// Type of network layer
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -18,11 +47,87 @@ pub enum LayerType {
     Bias,
 }
 
+/// When weight deltas computed for a pattern reach the connections
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum UpdateMode {
+    /// Apply every delta right after its pattern
+    #[default]
+    Online,
+    /// Accumulate deltas over the epoch and apply their sum once at its end
+    Batch,
+    /// Apply the accumulated deltas after every `size` patterns and after the final partial batch
+    MiniBatch { size: usize },
+}
+
+/// How the global learning rate evolves across epochs
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum LearningSchedule {
+    /// Always `learning_rate`
+    #[default]
+    Constant,
+    /// Bold driver: multiply the rate by `increase_factor` after an epoch whose error fell and by
+    /// `decrease_factor` after one whose error rose, keeping it within `[min_lr, max_lr]`
+    AdaptiveLr {
+        increase_factor: f64,
+        decrease_factor: f64,
+        min_lr: f64,
+        max_lr: f64,
+    },
+    /// Multiply the rate by `factor` once every `every` epochs
+    StepDecay { factor: f64, every: usize },
+    /// Multiply the rate by `gamma` every epoch
+    ExponentialDecay { gamma: f64 },
+}
+
+/// Learning rate overrides for connections leaving each layer, `None` falling back to `learning_rate`
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LayerLearningRates {
+    pub bias: Option<f64>,
+    pub input: Option<f64>,
+    pub hidden: Option<f64>,
+    pub output: Option<f64>,
+}
+
+impl LayerLearningRates {
+    /// Override for connections whose source neuron is in `layer`
+    pub fn for_layer(&self, layer: LayerType) -> Option<f64> {
+        match layer {
+            LayerType::Bias => self.bias,
+            LayerType::Input => self.input,
+            LayerType::Hidden => self.hidden,
+            LayerType::Output => self.output,
+        }
+    }
+}
+
+/// Fraction of excitatory neurons in each layer, `None` keeping the alternating layout
+///
+/// The bias pair and the all-excitatory output layer are fixed by the ED topology.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct LayerEiRatios {
+    pub input: Option<f64>,
+    pub hidden: Option<f64>,
+}
+
+impl LayerEiRatios {
+    /// Excitatory fraction requested for `layer`
+    pub fn for_layer(&self, layer: LayerType) -> Option<f64> {
+        match layer {
+            LayerType::Input => self.input,
+            LayerType::Hidden => self.hidden,
+            LayerType::Bias | LayerType::Output => None,
+        }
+    }
+}
+
 /// Network configuration flags controlling ED learning behavior
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct NetworkConfig {
     /// Number of recurrent processing timesteps per forward pass
     pub timesteps: usize,
+    /// Timesteps for `forward`, `predict` and `evaluate`, `timesteps` when `None`; training always uses `timesteps`
+    #[serde(default)]
+    pub inference_timesteps: Option<usize>,
     /// Learning rate controlling weight update magnitude
     pub learning_rate: f64,
     /// Global bias input applied to all neurons
@@ -47,6 +152,96 @@ pub struct NetworkConfig {
     pub flag_inhibitory_inputs: bool,
     /// Enable bidirectional error application mode
     pub mode_weight_decrement: bool,
+    /// Activation function of hidden and output neurons
+    #[serde(default)]
+    pub activation: Activation,
+    /// Activation function of the output layer, `activation` when `None`
+    #[serde(default)]
+    pub output_activation: Option<Activation>,
+    /// Per-neuron outputs or a softmax distribution that training and accuracy also use
+    #[serde(default)]
+    pub output_layer: OutputActivation,
+    /// Loss whose gradient seeds the output error channels
+    #[serde(default)]
+    pub loss: Loss,
+    /// Continuous or sampled 0/1 neuron outputs while training; `forward`, `predict` and `evaluate` stay continuous
+    ///
+    /// Sampled spikes drive the recurrent dynamics, while errors, updates and stats use the continuous activation.
+    #[serde(default)]
+    pub neuron_output_mode: NeuronOutputMode,
+    /// Magnitude of fixed inhibitory connections between every pair of distinct hidden neurons
+    ///
+    /// The connections carry weight `-magnitude` whatever the neuron types and are never trained;
+    /// they replace any learned hidden-to-hidden connections and need `timesteps >= 2` to act.
+    #[serde(default)]
+    pub lateral_inhibition: Option<f64>,
+    /// Standard deviation of zero-mean Gaussian noise added to every weight as it is read during
+    /// training's forward passes; stored weights stay clean and 0.0 disables it
+    ///
+    /// `predict_with_noise` and `evaluate_with_noise` apply it at inference time.
+    #[serde(default)]
+    pub weight_noise_std: f64,
+    /// Probability of silencing each hidden neuron for a whole training pattern, in `[0, 1)`
+    ///
+    /// Silenced neurons output 0.0 and the survivors are scaled by `1 / (1 - dropout_rate)`; the
+    /// draw comes from the network's RNG. Inference and `forward_sampled` never drop neurons.
+    /// Weight updates take the activation derivative before scaling, and skip dropped neurons.
+    #[serde(default)]
+    pub dropout_rate: f64,
+    /// How output errors are divided between the excitatory and inhibitory channels
+    #[serde(default)]
+    pub error_split: ErrorSplit,
+    /// Return the output layer as a softmax distribution from `forward` and `predict`
+    ///
+    /// Learning still uses the raw output activations; see `output_layer` to train on the distribution.
+    #[serde(default)]
+    pub softmax_output: bool,
+    /// Visit the training patterns in a fresh random order every epoch, drawn from the network's RNG
+    #[serde(default)]
+    pub shuffle_patterns: bool,
+    /// Apply weight deltas per pattern or once per epoch
+    #[serde(default)]
+    pub update_mode: UpdateMode,
+    /// Distribution of initial weights and thresholds, replacing `weight_init_range` and `threshold_init_range` when set
+    #[serde(default)]
+    pub weight_init: Option<WeightInit>,
+    /// Draw initial weights and thresholds from `[-range, range)` instead of `[0, range)`
+    #[serde(default)]
+    pub symmetric_init: bool,
+    /// Fraction of each connection's previous delta added to its next one, in `[0, 1)`
+    #[serde(default)]
+    pub momentum: f64,
+    /// L2 decay: enabled weights are multiplied by `1 - weight_decay` after every epoch
+    ///
+    /// Must lie in `[0, 1)`; 0.0 disables it and around 1e-4 is typical.
+    #[serde(default)]
+    pub weight_decay: f64,
+    /// `(min, max)` weight range enforced after every update, mirrored to `[-max, -min]` for
+    /// connections whose neuron types make them negative
+    #[serde(default)]
+    pub weight_bounds: Option<(f64, f64)>,
+    /// Symmetric limit: every weight is clamped to `[-limit, limit]` after each update, whatever its sign
+    #[serde(default)]
+    pub weight_clamp: Option<f64>,
+    /// Zero any weight an update pushes across the sign its neuron types prescribe (Dale's principle)
+    #[serde(default)]
+    pub enforce_sign_constraints: bool,
+    /// Give hidden and output neurons their own threshold, drawn from `threshold_init_range` and
+    /// trained per pattern alongside the bias-layer connections
+    #[serde(default)]
+    pub neuron_thresholds: bool,
+    /// Keep the bias-layer connections and neuron thresholds at their initial values
+    #[serde(default)]
+    pub freeze_thresholds: bool,
+    /// Per source-layer overrides of `learning_rate`
+    #[serde(default)]
+    pub layer_learning_rates: LayerLearningRates,
+    /// Per-layer excitatory fractions replacing the alternating neuron types
+    #[serde(default)]
+    pub ei_ratios: LayerEiRatios,
+    /// Epoch-by-epoch adjustment of `learning_rate`
+    #[serde(default)]
+    pub schedule: LearningSchedule,
 }
 
 impl Default for NetworkConfig {
@@ -54,6 +249,7 @@ impl Default for NetworkConfig {
     fn default() -> Self {
         Self {
             timesteps: 2,
+            inference_timesteps: None,
             learning_rate: 0.8,
             bias: 0.8,
             sigmoid_steepness: 0.4,
@@ -66,6 +262,319 @@ impl Default for NetworkConfig {
             flag_loop_cutting: true,
             flag_self_loop_cutting: true,
             flag_inhibitory_inputs: true,
+            activation: Activation::Sigmoid,
+            output_activation: None,
+            output_layer: OutputActivation::PerNeuron,
+            loss: Loss::Mse,
+            error_split: ErrorSplit::Hard,
+            neuron_output_mode: NeuronOutputMode::Deterministic,
+            weight_noise_std: 0.0,
+            dropout_rate: 0.0,
+            lateral_inhibition: None,
+            softmax_output: false,
+            shuffle_patterns: false,
+            update_mode: UpdateMode::Online,
+            weight_init: None,
+            symmetric_init: false,
+            momentum: 0.0,
+            weight_decay: 0.0,
+            weight_bounds: None,
+            weight_clamp: None,
+            enforce_sign_constraints: false,
+            neuron_thresholds: false,
+            freeze_thresholds: false,
+            layer_learning_rates: LayerLearningRates::default(),
+            ei_ratios: LayerEiRatios::default(),
+            schedule: LearningSchedule::Constant,
+        }
+    }
+}
+
+impl NetworkConfig {
+    /// Kaneko's published parameters, identical to `NetworkConfig::default()`
+    pub fn kaneko_default() -> Self {
+        Self::default()
+    }
+
+    /// Quicker, coarser training for experiments: a higher learning rate and a convergence
+    /// threshold twice the default
+    ///
+    /// Timesteps stay at the default 2. `flag_multilayer` cuts the input-to-output shortcuts, so the
+    /// outputs are two hops from the inputs and `dry_run` (see `check_output_reachability`) rejects a
+    /// single timestep.
+    pub fn fast_convergence() -> Self {
+        Self {
+            learning_rate: 1.2,
+            convergence_threshold: 0.2,
+            ..Self::default()
+        }
+    }
+
+    /// Slower, steadier training for long runs: half the default learning rate, a third timestep
+    /// and light weight decay
+    ///
+    /// Smaller steps overshoot less on noisy data, and the decay keeps weights from drifting into
+    /// sigmoid saturation over thousands of epochs. Expect about twice the epochs of the default.
+    pub fn stable() -> Self {
+        Self {
+            learning_rate: 0.4,
+            timesteps: 3,
+            weight_decay: 1e-4,
+            ..Self::default()
+        }
+    }
+
+    /// Recurrent processing: loop cutting off so hidden neurons listen to each other and to the
+    /// outputs, with 4 timesteps for that activity to circulate
+    ///
+    /// Initial weights are ten times smaller than the default so the feedback starts weak instead of
+    /// locking the network into a saturated state. Recurrent ED converges less reliably than the
+    /// feedforward presets; try several seeds.
+    pub fn deep() -> Self {
+        Self {
+            timesteps: 4,
+            flag_loop_cutting: false,
+            weight_init_range: 0.1,
+            ..Self::default()
+        }
+    }
+
+    /// Strictly layered feedforward topology: multilayer mode and every loop-cutting flag on,
+    /// inhibitory input connections off
+    ///
+    /// Without the inhibitory input copies, tasks such as XOR that need them are not learnable.
+    pub fn feedforward_strict() -> Self {
+        Self {
+            flag_multilayer: true,
+            flag_loop_cutting: true,
+            flag_self_loop_cutting: true,
+            flag_inhibitory_inputs: false,
+            ..Self::default()
+        }
+    }
+
+    /// Reject parameter values that would otherwise produce NaN or no learning at all
+    ///
+    /// Network constructors and `train_epoch` call this themselves.
+    pub fn validate(&self) -> Result<(), EdError> {
+        if self.timesteps == 0 {
+            return Err(EdError::InvalidConfig("timesteps must be at least 1".into()));
+        }
+        if self.inference_timesteps == Some(0) {
+            return Err(EdError::InvalidConfig("inference_timesteps must be at least 1".into()));
+        }
+        if !(self.learning_rate.is_finite() && self.learning_rate > 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "learning_rate must be positive, got {}",
+                self.learning_rate
+            )));
+        }
+        if !(self.sigmoid_steepness.is_finite() && self.sigmoid_steepness > 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "sigmoid_steepness must be positive, got {}",
+                self.sigmoid_steepness
+            )));
+        }
+        if !(self.weight_init_range.is_finite() && self.weight_init_range >= 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "weight_init_range must be non-negative, got {}",
+                self.weight_init_range
+            )));
+        }
+        if !(self.threshold_init_range.is_finite() && self.threshold_init_range >= 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "threshold_init_range must be non-negative, got {}",
+                self.threshold_init_range
+            )));
+        }
+        match self.weight_init {
+            Some(WeightInit::Uniform {
+                range,
+            }) if !(range.is_finite() && range >= 0.0) => {
+                return Err(EdError::InvalidConfig(format!(
+                    "weight_init range must be non-negative, got {}",
+                    range
+                )));
+            }
+            Some(WeightInit::Gaussian {
+                mean,
+                std,
+            }) if !(mean.is_finite() && std.is_finite() && std >= 0.0) => {
+                return Err(EdError::InvalidConfig(format!(
+                    "weight_init needs a finite mean and non-negative std, got {} and {}",
+                    mean, std
+                )));
+            }
+            _ => {}
+        }
+        if !(self.convergence_threshold.is_finite() && self.convergence_threshold >= 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "convergence_threshold must be non-negative, got {}",
+                self.convergence_threshold
+            )));
+        }
+        if !(self.error_amplification.is_finite() && self.error_amplification >= 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "error_amplification must be non-negative, got {}",
+                self.error_amplification
+            )));
+        }
+        if !self.bias.is_finite() {
+            return Err(EdError::InvalidConfig(format!("bias must be finite, got {}", self.bias)));
+        }
+        let overrides = self.layer_learning_rates;
+        for rate in [overrides.bias, overrides.input, overrides.hidden, overrides.output]
+            .into_iter()
+            .flatten()
+        {
+            if !(rate.is_finite() && rate > 0.0) {
+                return Err(EdError::InvalidConfig(format!(
+                    "layer learning rates must be positive, got {}",
+                    rate
+                )));
+            }
+        }
+        if let ErrorSplit::Soft {
+            temperature,
+        } = self.error_split
+            && !(temperature.is_finite() && temperature > 0.0)
+        {
+            return Err(EdError::InvalidConfig(format!(
+                "soft error split temperature must be positive, got {}",
+                temperature
+            )));
+        }
+        for ratio in [self.ei_ratios.input, self.ei_ratios.hidden].into_iter().flatten() {
+            if !(0.0..=1.0).contains(&ratio) {
+                return Err(EdError::InvalidConfig(format!("ei ratios must lie in [0, 1], got {}", ratio)));
+            }
+        }
+        if let LearningSchedule::AdaptiveLr {
+            increase_factor,
+            decrease_factor,
+            min_lr,
+            max_lr,
+        } = self.schedule
+            && !(increase_factor >= 1.0 && decrease_factor > 0.0 && decrease_factor <= 1.0 && min_lr > 0.0 && min_lr <= max_lr)
+        {
+            return Err(EdError::InvalidConfig(
+                "adaptive learning rate needs increase_factor >= 1, decrease_factor in (0, 1] and 0 < min_lr <= max_lr".into(),
+            ));
+        }
+        match self.schedule {
+            LearningSchedule::StepDecay {
+                factor,
+                every,
+            } if !(factor > 0.0 && factor <= 1.0 && every > 0) => {
+                return Err(EdError::InvalidConfig(format!(
+                    "step decay needs factor in (0, 1] and every >= 1, got factor {} every {}",
+                    factor, every
+                )));
+            }
+            LearningSchedule::ExponentialDecay {
+                gamma,
+            } if !(gamma > 0.0 && gamma <= 1.0) => {
+                return Err(EdError::InvalidConfig(format!(
+                    "exponential decay gamma must be in (0, 1], got {}",
+                    gamma
+                )));
+            }
+            _ => {}
+        }
+        if let Some((min, max)) = self.weight_bounds
+            && !(min.is_finite() && max.is_finite() && 0.0 <= min && min <= max)
+        {
+            return Err(EdError::InvalidConfig(format!(
+                "weight_bounds must satisfy 0 <= min <= max, got ({}, {})",
+                min, max
+            )));
+        }
+        if let Some(limit) = self.weight_clamp
+            && !(limit.is_finite() && limit > 0.0)
+        {
+            return Err(EdError::InvalidConfig(format!("weight_clamp must be positive, got {}", limit)));
+        }
+        if !(0.0..1.0).contains(&self.momentum) {
+            return Err(EdError::InvalidConfig(format!("momentum must be in [0, 1), got {}", self.momentum)));
+        }
+        if let Some(magnitude) = self.lateral_inhibition
+            && !(magnitude.is_finite() && magnitude > 0.0)
+        {
+            return Err(EdError::InvalidConfig(format!(
+                "lateral_inhibition must be positive, got {}",
+                magnitude
+            )));
+        }
+        if !(self.weight_noise_std.is_finite() && self.weight_noise_std >= 0.0) {
+            return Err(EdError::InvalidConfig(format!(
+                "weight_noise_std must be non-negative, got {}",
+                self.weight_noise_std
+            )));
+        }
+        if !(0.0..1.0).contains(&self.dropout_rate) {
+            return Err(EdError::InvalidConfig(format!(
+                "dropout_rate must be in [0, 1), got {}",
+                self.dropout_rate
+            )));
+        }
+        if !(0.0..1.0).contains(&self.weight_decay) {
+            return Err(EdError::InvalidConfig(format!(
+                "weight_decay must be in [0, 1), got {}",
+                self.weight_decay
+            )));
+        }
+        if matches!(
+            self.update_mode,
+            UpdateMode::MiniBatch {
+                size: 0
+            }
+        ) {
+            return Err(EdError::InvalidConfig("mini-batch size must be at least 1".into()));
+        }
+
+        Ok(())
+    }
+
+    /// Activation derivative at `output` for a neuron in `layer`, as used by weight updates
+    ///
+    /// A softmax output layer uses the diagonal of the softmax Jacobian.
+    fn derivative_for(&self, layer: LayerType, output: f64) -> f64 {
+        if layer == LayerType::Output && self.output_layer == OutputActivation::Softmax {
+            sigmoid_derivative(output)
+        } else {
+            self.activation_for(layer).derivative(output)
+        }
+    }
+
+    /// `derivative_for` at the activation a neuron had before dropout scaled its output by `scale`
+    ///
+    /// A dropped neuron (scale 0) gets 0, so it does not learn from a pattern it sat out.
+    fn dropout_derivative(&self, layer: LayerType, output: f64, scale: Option<f64>) -> f64 {
+        match scale {
+            Some(0.0) => 0.0,
+            Some(scale) => self.derivative_for(layer, output / scale),
+            None => self.derivative_for(layer, output),
+        }
+    }
+
+    /// Hold a just-updated weight inside `weight_bounds` and `weight_clamp`, returning whether it moved
+    fn limit_weight(&self, connection: &mut Connection, sign: f64) -> bool {
+        let before = connection.weight;
+        if let Some(bounds) = self.weight_bounds {
+            connection.clamp_to_bounds(bounds, sign);
+        }
+        if let Some(limit) = self.weight_clamp {
+            connection.clamp_weight(limit);
+        }
+
+        connection.weight != before
+    }
+
+    /// Activation function applied by neurons in `layer`
+    pub fn activation_for(&self, layer: LayerType) -> Activation {
+        match layer {
+            LayerType::Output => self.output_activation.unwrap_or(self.activation),
+            _ => self.activation,
         }
     }
 }
@@ -84,12 +593,18 @@ pub struct NetworkLayer {
 impl NetworkLayer {
     /// Create new layer with specified type and size
     pub fn new(layer_type: LayerType, size: usize, layer_index: usize) -> Self {
+        Self::with_ei_ratio(layer_type, size, layer_index, None)
+    }
+
+    /// Create a layer whose first `⌈ei_ratio · size⌉` neurons are excitatory, alternating when `None`
+    pub fn with_ei_ratio(layer_type: LayerType, size: usize, layer_index: usize, ei_ratio: Option<f64>) -> Self {
         let mut neurons = Vec::with_capacity(size);
 
         for i in 0..size {
-            let neuron_type = match layer_type {
-                LayerType::Output => NeuronType::Excitatory, // Output always excitatory
-                _ => NeuronType::from_index(i),              // Alternating pattern for others
+            let neuron_type = match (layer_type, ei_ratio) {
+                (LayerType::Output, _) => NeuronType::Excitatory, // Output always excitatory
+                (_, Some(ratio)) => NeuronType::from_ratio(i, size, ratio),
+                (_, None) => NeuronType::from_index(i), // Alternating pattern for others
             };
             neurons.push(Neuron::new(neuron_type, i));
         }
@@ -136,6 +651,25 @@ pub struct LearningStats {
     pub converged: bool,
     /// Final accuracy percentage
     pub accuracy: f64,
+    /// Summed absolute validation error after each epoch of validated training
+    #[serde(default)]
+    pub validation_history: Vec<f64>,
+    /// Epoch with the lowest validation error, whose weights were restored unless training converged
+    #[serde(default)]
+    pub best_epoch: Option<usize>,
+    /// Criterion that ended the last training run
+    #[serde(default)]
+    pub stop_reason: Option<StopReason>,
+    /// Running totals of online `train_pattern` calls, kept apart from the epoch figures above
+    #[serde(default)]
+    pub online: OnlineStats,
+    /// Weight updates moved back inside `config.weight_bounds` or `config.weight_clamp`, counted over
+    /// the network's lifetime
+    #[serde(default)]
+    pub clipped_updates: usize,
+    /// Global learning rate used by each epoch, as set by `config.schedule`
+    #[serde(default)]
+    pub learning_rate_history: Vec<f64>,
 }
 
 impl LearningStats {
@@ -153,7 +687,26 @@ impl LearningStats {
         self.total_error = total_error;
         self.error_count = error_count;
         self.error_history.push(total_error);
-        self.accuracy = 100.0 * (self.pattern_count - error_count) as f64 / self.pattern_count as f64;
+        self.accuracy = match self.pattern_count {
+            0 => 0.0,
+            count => 100.0 * count.saturating_sub(error_count) as f64 / count as f64,
+        };
+    }
+
+    /// Learning curve as CSV: an `epoch,error` header, then one row per epoch counted from 1
+    pub fn to_csv_string(&self) -> String {
+        let mut csv = String::from("epoch,error\n");
+
+        for (epoch, error) in self.error_history.iter().enumerate() {
+            csv.push_str(&format!("{},{}\n", epoch + 1, error));
+        }
+
+        csv
+    }
+
+    /// Write `to_csv_string` to `path`
+    pub fn write_error_history_csv<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        std::fs::write(path, self.to_csv_string())
     }
 
     /// Check if learning has converged
@@ -164,7 +717,40 @@ impl LearningStats {
 
     /// Get current error rate
     pub fn error_rate(&self) -> f64 {
-        self.error_count as f64 / self.pattern_count as f64
+        match self.pattern_count {
+            0 => 0.0,
+            count => self.error_count as f64 / count as f64,
+        }
+    }
+}
+
+/// Statistics of online `train_pattern` calls, over the latest result of each pattern id seen
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct OnlineStats {
+    /// Latest `(error, misclassified)` of every pattern id
+    pub patterns: BTreeMap<usize, (f64, bool)>,
+    /// Summed latest error of every pattern id
+    pub total_error: f64,
+    /// Pattern ids whose latest presentation was misclassified
+    pub error_count: usize,
+    /// Number of distinct pattern ids seen
+    pub pattern_count: usize,
+    /// Percentage of pattern ids whose latest presentation was classified correctly
+    pub accuracy: f64,
+}
+
+impl OnlineStats {
+    /// Record one online presentation, replacing the previous result of the same pattern id
+    pub fn record_pattern(&mut self, id: usize, error: f64, misclassified: bool) {
+        if let Some((old_error, old_misclassified)) = self.patterns.insert(id, (error, misclassified)) {
+            self.total_error -= old_error;
+            self.error_count = self.error_count.saturating_sub(old_misclassified as usize);
+        }
+
+        self.total_error += error;
+        self.error_count += misclassified as usize;
+        self.pattern_count = self.patterns.len();
+        self.accuracy = 100.0 * self.pattern_count.saturating_sub(self.error_count) as f64 / self.pattern_count as f64;
     }
 }
 
@@ -176,16 +762,118 @@ impl fmt::Display for LearningStats {
             self.epoch,
             self.total_error,
             self.accuracy,
-            self.pattern_count - self.error_count,
+            self.pattern_count.saturating_sub(self.error_count),
             self.pattern_count
         )
     }
 }
 
+/// Connections whose non-finite weights were zeroed and disabled on load
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RepairReport {
+    /// `(from, to)` coordinates of the repaired connections, in row-major order
+    pub repaired: Vec<(usize, usize)>,
+}
+
+impl RepairReport {
+    /// Whether nothing needed repairing
+    pub fn is_empty(&self) -> bool {
+        self.repaired.is_empty()
+    }
+}
+
+/// Connection whose weight sign contradicts the product of its endpoints' neuron-type factors
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SignViolation {
+    pub from: usize,
+    pub to: usize,
+    pub weight: f64,
+    /// Sign prescribed by the neuron types, `1.0` or `-1.0`
+    pub expected_sign: f64,
+}
+
+/// Distribution of the enabled connection weights and output saturation, for spotting divergence
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub struct WeightStats {
+    /// Number of enabled connections the weight figures cover; all figures are 0 when there are none
+    pub enabled_connections: usize,
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    /// Population standard deviation
+    pub std_dev: f64,
+    /// Output neurons whose last output is below 0.01 or above 0.99
+    pub saturated_outputs: usize,
+}
+
+impl fmt::Display for WeightStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Weights:{} Min:{:.4} Max:{:.4} Mean:{:.4} Std:{:.4} Saturated:{}",
+            self.enabled_connections, self.min, self.max, self.mean, self.std_dev, self.saturated_outputs
+        )
+    }
+}
+
+/// Class tallies over a dataset: `counts[actual][predicted]`
+///
+/// Classes are argmax indices, or `output >= 0.5` for a single-output network.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ConfusionMatrix {
+    pub counts: Vec<Vec<usize>>,
+}
+
+impl ConfusionMatrix {
+    /// Number of classes along each side
+    pub fn classes(&self) -> usize {
+        self.counts.len()
+    }
+}
+
+impl fmt::Display for ConfusionMatrix {
+    /// Rows are actual classes and columns predicted ones, each labeled with its class index
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let largest = self
+            .counts
+            .iter()
+            .flatten()
+            .copied()
+            .max()
+            .unwrap_or(0)
+            .max(self.classes().saturating_sub(1));
+        let width = largest.to_string().len();
+
+        write!(f, "{:width$}", "")?;
+        for class in 0..self.classes() {
+            write!(f, " {:>width$}", class)?;
+        }
+        for (actual, row) in self.counts.iter().enumerate() {
+            write!(f, "\n{:>width$}", actual)?;
+            for count in row {
+                write!(f, " {:>width$}", count)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Accuracy and error of a network over a dataset, measured with forward passes only
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EvalReport {
+    /// Percentage of patterns whose every output is within the threshold of its target
+    pub accuracy: f64,
+    /// Mean squared error over every output of every pattern
+    pub mean_squared_error: f64,
+    /// Summed absolute error of each pattern, in dataset order
+    pub per_pattern_errors: Vec<f64>,
+}
+
 /// Network dimensional parameters
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct NetworkDimensions {
-    /// Number of input neurons (logical inputs, will be doubled)
+    /// Number of input neurons (logical inputs, doubled when `inhibitory_inputs` is set)
     pub input_size: usize,
     /// Number of hidden neurons
     pub hidden_size: usize,
@@ -193,20 +881,61 @@ pub struct NetworkDimensions {
     pub output_size: usize,
     /// Total number of neurons
     pub total_neurons: usize,
+    /// Whether each logical input gets an inhibitory copy next to its excitatory one
+    #[serde(default = "default_inhibitory_inputs")]
+    pub inhibitory_inputs: bool,
+}
+
+/// Networks saved before the input mode was recorded always had paired inputs
+fn default_inhibitory_inputs() -> bool {
+    true
 }
 
 impl NetworkDimensions {
     /// Create new network dimensions
     pub fn new(input_size: usize, hidden_size: usize, output_size: usize) -> Self {
-        let total_neurons = 2 + (input_size * 2) + hidden_size + output_size; // +2 for bias
-
         Self {
             input_size,
             hidden_size,
             output_size,
-            total_neurons,
+            total_neurons: 0,
+            inhibitory_inputs: true,
+        }
+        .with_totals()
+    }
+
+    /// Same sizes with or without the inhibitory input copies, recomputing `total_neurons`
+    pub fn with_inhibitory_inputs(self, inhibitory_inputs: bool) -> Self {
+        Self {
+            inhibitory_inputs,
+            ..self
+        }
+        .with_totals()
+    }
+
+    /// Neurons built per logical input: 2 with inhibitory copies, 1 otherwise
+    pub fn input_copies(&self) -> usize {
+        if self.inhibitory_inputs {
+            2
+        } else {
+            1
         }
     }
+
+    /// Size of the input layer
+    pub fn input_neurons(&self) -> usize {
+        self.input_size.saturating_mul(self.input_copies())
+    }
+
+    fn with_totals(mut self) -> Self {
+        // +2 for bias; saturates so oversized requests fail the MAX_NETWORK_SIZE check instead of overflowing
+        self.total_neurons = self
+            .input_neurons()
+            .saturating_add(self.hidden_size)
+            .saturating_add(self.output_size)
+            .saturating_add(2);
+        self
+    }
 }
 
 /// Main Error Diffusion Neural Network structure
@@ -224,4 +953,1809 @@ pub struct EDNetwork {
     pub stats: LearningStats,
     /// Training patterns
     pub training_data: Vec<TrainingPattern>,
+    /// Optional research hook adjusting every weight update (not serialized)
+    #[serde(skip)]
+    pub update_hook: Option<Box<dyn ConnectionUpdateHook>>,
+    /// Whether any update went through a hook, so the weights cannot be replayed from the config alone
+    #[serde(default)]
+    pub trained_with_hook: bool,
+    /// Seed of the `StdRng` that drew the initial weights, if the network was built from one
+    #[serde(default)]
+    pub seed: Option<u64>,
+    /// Generator behind pattern shuffling, seeded from the weight-init RNG so seeded runs replay exactly
+    #[serde(default = "fresh_rng")]
+    pub(crate) rng: Xoshiro256PlusPlus,
+    /// Deltas awaiting `apply_accumulated_deltas` in batch mode, shaped like `connections` once used
+    #[serde(skip)]
+    pending_deltas: Vec<Vec<f64>>,
+    /// Output scale of each hidden neuron in the last forward pass, empty when nothing was dropped
+    #[serde(skip)]
+    dropout_scales: Vec<f64>,
+}
+
+/// Unseeded generator for networks saved before the RNG state was serialized
+fn fresh_rng() -> Xoshiro256PlusPlus {
+    Xoshiro256PlusPlus::from_rng(&mut rand::rng())
+}
+
+/// Scratch space for `EDNetwork::predict_into`, reused across calls so steady-state inference
+/// does not allocate
+///
+/// One set of buffers serves any network; they grow to the largest network they have been used with.
+#[derive(Debug, Clone, Default)]
+pub struct PredictBuffers {
+    outputs: Vec<f64>,
+    sums: Vec<f64>,
+    previous: Vec<f64>,
+    dropout_scales: Vec<f64>,
+}
+
+/// Which configured perturbations a forward pass applies
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pass {
+    /// Deterministic firing over the stored weights
+    Clean,
+    /// Deterministic firing with `config.weight_noise_std`
+    Noisy,
+    /// `config.neuron_output_mode` and `config.weight_noise_std`
+    Sampled,
+    /// `Sampled` plus `config.dropout_rate`
+    Training,
+}
+
+impl EDNetwork {
+    /// Create new network with bias, doubled input, hidden and output layers fully wired under the config flags
+    pub fn new(dimensions: NetworkDimensions, config: NetworkConfig) -> Result<Self, EdError> {
+        Self::new_with_rng(dimensions, config, &mut rand::rng())
+    }
+
+    /// Create a network whose weights and thresholds are drawn from `StdRng::seed_from_u64(seed)`
+    ///
+    /// Identical seeds, dimensions and configs give bit-identical connections.
+    pub fn new_seeded(dimensions: NetworkDimensions, config: NetworkConfig, seed: u64) -> Result<Self, EdError> {
+        let mut network = Self::new_with_rng(dimensions, config, &mut StdRng::seed_from_u64(seed))?;
+        network.seed = Some(seed);
+        Ok(network)
+    }
+
+    /// Create a network whose initial weights and thresholds are all drawn from `rng`
+    pub fn new_with_rng<R: Rng>(dimensions: NetworkDimensions, config: NetworkConfig, rng: &mut R) -> Result<Self, EdError> {
+        // The config flag decides the input layout, whatever the caller's dimensions said
+        let dimensions = dimensions.with_inhibitory_inputs(config.flag_inhibitory_inputs);
+        if dimensions.input_size == 0 {
+            return Err(EdError::InvalidConfig("network needs at least one input".into()));
+        }
+        if dimensions.hidden_size == 0 {
+            return Err(EdError::InvalidConfig("network needs at least one hidden neuron".into()));
+        }
+        if dimensions.output_size == 0 {
+            return Err(EdError::InvalidConfig("network needs at least one output".into()));
+        }
+        if dimensions.total_neurons > MAX_NETWORK_SIZE {
+            return Err(EdError::NetworkTooLarge {
+                neurons: dimensions.total_neurons,
+                max: MAX_NETWORK_SIZE,
+            });
+        }
+        config.validate()?;
+
+        Ok(Self::build(dimensions, config, rng))
+    }
+
+    /// Allocate the layers and connection matrix, drawing initial weights from `rng`
+    fn build<R: Rng>(dimensions: NetworkDimensions, config: NetworkConfig, rng: &mut R) -> Self {
+        // Without inhibitory copies every input neuron is excitatory
+        let input_ratio = if dimensions.inhibitory_inputs {
+            config.ei_ratios.input
+        } else {
+            Some(1.0)
+        };
+        let mut layers = vec![
+            NetworkLayer::new(LayerType::Bias, 2, 0),
+            NetworkLayer::with_ei_ratio(LayerType::Input, dimensions.input_neurons(), 1, input_ratio),
+            NetworkLayer::with_ei_ratio(LayerType::Hidden, dimensions.hidden_size, 2, config.ei_ratios.hidden),
+            NetworkLayer::new(LayerType::Output, dimensions.output_size, 3),
+        ];
+        let neurons: Vec<(LayerType, NeuronType)> = layers
+            .iter()
+            .flat_map(|layer| layer.neurons.iter().map(|neuron| (layer.layer_type, neuron.neuron_type)))
+            .collect();
+
+        let uniform = if config.symmetric_init {
+            random_weight_symmetric::<R>
+        } else {
+            random_weight::<R>
+        };
+        // Enabled connections into each neuron, only needed for fan-in scaling
+        let fan_in: Vec<usize> = match config.weight_init {
+            Some(WeightInit::ScaledByFanIn) => (0..neurons.len())
+                .map(|to| {
+                    (0..neurons.len())
+                        .filter(|&from| Self::is_connection_allowed(&config, from, to, neurons[from], neurons[to].0))
+                        .count()
+                })
+                .collect(),
+            _ => Vec::new(),
+        };
+        let draw = |rng: &mut R, range: f64, to: usize| match config.weight_init {
+            None => uniform(rng, range),
+            Some(WeightInit::Uniform {
+                range,
+            }) => uniform(rng, range),
+            Some(WeightInit::Gaussian {
+                mean,
+                std,
+            }) => random_gaussian(rng, mean, std),
+            Some(WeightInit::ScaledByFanIn) => uniform(rng, 1.0 / (fan_in[to].max(1) as f64).sqrt()),
+        };
+        let mut connections = Vec::with_capacity(neurons.len());
+
+        for (from, &(from_layer, from_type)) in neurons.iter().enumerate() {
+            let mut row = Vec::with_capacity(neurons.len());
+
+            for (to, &(to_layer, to_type)) in neurons.iter().enumerate() {
+                if let Some(magnitude) = config.lateral_inhibition
+                    && from != to
+                    && from_layer == LayerType::Hidden
+                    && to_layer == LayerType::Hidden
+                {
+                    row.push(Connection::fixed(from, to, -magnitude));
+                    continue;
+                }
+                let enabled = Self::is_connection_allowed(&config, from, to, (from_layer, from_type), to_layer);
+                let base_weight = match (enabled, from_layer) {
+                    (false, _) => 0.0,
+                    (true, LayerType::Bias) => draw(rng, config.threshold_init_range, to),
+                    (true, _) => draw(rng, config.weight_init_range, to),
+                };
+
+                let mut connection = Connection::new(from, to, base_weight, from_type, to_type);
+                connection.connection_enabled = enabled;
+                row.push(connection);
+            }
+
+            connections.push(row);
+        }
+
+        // Drawn after the weights so networks without neuron thresholds keep their seeded weights
+        if config.neuron_thresholds {
+            for (index, (neuron, &(layer, _))) in layers.iter_mut().flat_map(|l| l.neurons.iter_mut()).zip(&neurons).enumerate() {
+                if matches!(layer, LayerType::Hidden | LayerType::Output) {
+                    neuron.threshold = draw(rng, config.threshold_init_range, index);
+                }
+            }
+        }
+
+        Self {
+            layers,
+            connections,
+            config,
+            dimensions,
+            stats: LearningStats::new(0),
+            training_data: Vec::new(),
+            update_hook: None,
+            trained_with_hook: false,
+            seed: None,
+            rng: Xoshiro256PlusPlus::from_rng(rng),
+            pending_deltas: Vec::new(),
+            dropout_scales: Vec::new(),
+        }
+    }
+
+    /// Route every subsequent weight update through `hook`
+    pub fn set_update_hook<H: ConnectionUpdateHook + 'static>(&mut self, hook: H) {
+        self.update_hook = Some(Box::new(hook));
+    }
+
+    /// Whether the weights can be reproduced from the config and data alone (no hook was involved)
+    pub fn is_replayable(&self) -> bool {
+        !self.trained_with_hook
+    }
+
+    /// Clear neuron states, pending batch deltas and `stats`, keeping the learned weights
+    pub fn reset(&mut self) {
+        for layer in &mut self.layers {
+            layer.reset();
+        }
+        self.pending_deltas.clear();
+        self.stats = LearningStats::new(self.training_data.len());
+    }
+
+    /// Restart training from scratch: redraw every weight and threshold as `new_seeded` would, then `reset`
+    ///
+    /// The config, training data and update hook are kept. Fails if the config has been edited into
+    /// an invalid state.
+    pub fn reset_weights(&mut self, seed: u64) -> Result<(), EdError> {
+        let fresh = Self::new_seeded(self.dimensions.clone(), self.config.clone(), seed)?;
+
+        self.layers = fresh.layers;
+        self.connections = fresh.connections;
+        self.rng = fresh.rng;
+        self.seed = fresh.seed;
+        self.trained_with_hook = false;
+        self.reset();
+
+        Ok(())
+    }
+
+    /// Topology rules from Kaneko's reference initialization
+    fn is_connection_allowed(
+        config: &NetworkConfig,
+        from: usize,
+        to: usize,
+        (from_layer, from_type): (LayerType, NeuronType),
+        to_layer: LayerType,
+    ) -> bool {
+        // Bias and input neurons are clamped, nothing feeds them
+        if matches!(to_layer, LayerType::Bias | LayerType::Input) {
+            return false;
+        }
+
+        if from == to {
+            return !config.flag_self_loop_cutting;
+        }
+
+        // Loop cutting keeps hidden neurons from listening to each other or to the output
+        if config.flag_loop_cutting {
+            match (from_layer, to_layer) {
+                (LayerType::Hidden, LayerType::Hidden) | (LayerType::Output, _) => return false,
+                _ => {}
+            }
+        }
+
+        // Multi-layer processing routes every input through the hidden layer
+        if config.flag_multilayer && from_layer == LayerType::Input && to_layer == LayerType::Output {
+            return false;
+        }
+
+        if !config.flag_inhibitory_inputs && from_layer == LayerType::Input && from_type == NeuronType::Inhibitory {
+            return false;
+        }
+
+        true
+    }
+
+    /// Run a pattern through the network and return the output layer activations
+    ///
+    /// Each logical input drives its excitatory/inhibitory input pair, the bias layer carries `config.bias`,
+    /// and `config.inference_timesteps` (default `config.timesteps`) recurrent updates are applied over
+    /// the enabled connections.
+    pub fn forward(&mut self, inputs: &[f64]) -> Result<Vec<f64>, EdError> {
+        self.check_inputs(inputs)?;
+        self.propagate(inputs, self.inference_timesteps(), Pass::Clean);
+
+        self.normalize_outputs(self.output_values())
+    }
+
+    /// `forward` with training's `config.neuron_output_mode` and `config.weight_noise_std` applied,
+    /// drawing spikes and noise from the network's RNG
+    ///
+    /// Returns the continuous output activations, like training does.
+    pub fn forward_sampled(&mut self, inputs: &[f64]) -> Result<Vec<f64>, EdError> {
+        self.check_inputs(inputs)?;
+        self.propagate(inputs, self.inference_timesteps(), Pass::Sampled);
+
+        self.normalize_outputs(self.output_values())
+    }
+
+    /// Inference-only forward pass on scratch buffers
+    ///
+    /// Produces the same outputs as `forward` but leaves neuron states untouched, so a trained
+    /// network can be shared (e.g. behind an `Arc`) and queried from several threads.
+    pub fn predict(&self, inputs: &[f64]) -> Result<Vec<f64>, EdError> {
+        self.predict_with_timesteps(inputs, self.inference_timesteps())
+    }
+
+    /// `predict` with an explicit number of recurrent timesteps, at least 1
+    #[deny(clippy::indexing_slicing)]
+    pub fn predict_with_timesteps(&self, inputs: &[f64], timesteps: usize) -> Result<Vec<f64>, EdError> {
+        self.check_inputs(inputs)?;
+        if timesteps == 0 {
+            return Err(EdError::InvalidConfig("timesteps must be at least 1".into()));
+        }
+
+        // Neither sampling nor noise is on, so the RNG copy is never drawn from
+        let mut buffers = PredictBuffers::default();
+        self.predict_on(inputs, timesteps, Pass::Clean, &mut self.rng.clone(), &mut buffers)
+            .map(<[f64]>::to_vec)
+    }
+
+    /// `predict` into caller-owned `buffers`, returning the outputs as a slice of them
+    ///
+    /// Once the buffers have grown to the network's size this allocates nothing, unless a softmax
+    /// output layer is configured.
+    pub fn predict_into<'a>(&self, inputs: &[f64], buffers: &'a mut PredictBuffers) -> Result<&'a [f64], EdError> {
+        self.check_inputs(inputs)?;
+
+        self.predict_on(inputs, self.inference_timesteps(), Pass::Clean, &mut self.rng.clone(), buffers)
+    }
+
+    /// `predict` with `config.weight_noise_std` applied to the weights, drawing the noise from `rng`
+    ///
+    /// Comparing against `predict` measures how robust the network is to imprecise weights.
+    pub fn predict_with_noise<R: Rng>(&self, inputs: &[f64], rng: &mut R) -> Result<Vec<f64>, EdError> {
+        self.check_inputs(inputs)?;
+
+        let mut buffers = PredictBuffers::default();
+        self.predict_on(inputs, self.inference_timesteps(), Pass::Noisy, rng, &mut buffers)
+            .map(<[f64]>::to_vec)
+    }
+
+    fn predict_on<'a, R: Rng>(
+        &self,
+        inputs: &[f64],
+        timesteps: usize,
+        pass: Pass,
+        rng: &mut R,
+        buffers: &'a mut PredictBuffers,
+    ) -> Result<&'a [f64], EdError> {
+        self.run_recurrent(inputs, timesteps, pass, rng, buffers);
+        let output_range = self.layer_range(LayerType::Output);
+        let outputs = buffers.outputs.get_mut(output_range).unwrap_or_default();
+
+        self.normalize_in_place(outputs)?;
+        Ok(outputs)
+    }
+
+    /// Timesteps used outside training
+    fn inference_timesteps(&self) -> usize {
+        self.config.inference_timesteps.unwrap_or(self.config.timesteps)
+    }
+
+    /// Apply the configured output normalization to returned predictions, rejecting non-finite outputs
+    fn normalize_outputs(&self, mut outputs: Vec<f64>) -> Result<Vec<f64>, EdError> {
+        self.normalize_in_place(&mut outputs)?;
+        Ok(outputs)
+    }
+
+    /// `normalize_outputs` without allocating
+    fn normalize_in_place(&self, outputs: &mut [f64]) -> Result<(), EdError> {
+        if let Some(output) = outputs.iter().find(|output| !output.is_finite()) {
+            return Err(EdError::NumericalInstability(format!("network produced output {}", output)));
+        }
+
+        // A softmax output layer already produces a distribution
+        if self.config.softmax_output && self.config.output_layer == OutputActivation::PerNeuron {
+            let max = outputs.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+            let mut sum = 0.0;
+            for output in outputs.iter_mut() {
+                *output = (*output - max).exp();
+                sum += *output;
+            }
+            for output in outputs.iter_mut() {
+                *output /= sum;
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Run `predict` over several input vectors
+    pub fn predict_batch(&self, inputs: &[Vec<f64>]) -> Result<Vec<Vec<f64>>, EdError> {
+        inputs.iter().map(|input| self.predict(input)).collect()
+    }
+
+    /// Verify an input vector matches the logical input size and is finite
+    fn check_inputs(&self, inputs: &[f64]) -> Result<(), EdError> {
+        self.check_layout()?;
+        if inputs.len() != self.dimensions.input_size {
+            return Err(EdError::DimensionMismatch {
+                expected: self.dimensions.input_size,
+                got: inputs.len(),
+            });
+        }
+        if let Some(value) = inputs.iter().find(|value| !value.is_finite()) {
+            return Err(EdError::NumericalInstability(format!("input value {} is not finite", value)));
+        }
+
+        Ok(())
+    }
+
+    /// Run one forward pass and one ED weight update for a single pattern
+    ///
+    /// The pattern does not need to be in `training_data`. `stats.online` tracks the latest error of
+    /// every pattern id seen this way, so its `pattern_count` grows as new ids arrive, while the
+    /// epoch figures of `stats` are left to `train_epoch`. Returns the
+    /// summed absolute prediction error measured before the update.
+    pub fn train_pattern(&mut self, pattern: &TrainingPattern) -> Result<f64, EdError> {
+        self.check_patterns(std::slice::from_ref(pattern))?;
+
+        let (error, misclassified) = self.learn_pattern(pattern);
+        self.apply_accumulated_deltas();
+        self.stats.online.record_pattern(pattern.id, error, misclassified);
+
+        Ok(error)
+    }
+
+    /// Forward, diffuse and update for a validated pattern, returning its error and whether it was misclassified
+    fn learn_pattern(&mut self, pattern: &TrainingPattern) -> (f64, bool) {
+        self.propagate(&pattern.inputs, self.config.timesteps, Pass::Training);
+
+        let misclassified = self.is_misclassified(&self.output_values(), &pattern.targets);
+        let error = self.diffuse_error(&pattern.targets);
+
+        self.update_weights();
+
+        (error, misclassified)
+    }
+
+    /// Shrink every enabled weight by `config.weight_decay`, respecting frozen thresholds and weight limits
+    fn decay_weights(&mut self) {
+        if self.config.weight_decay == 0.0 {
+            return;
+        }
+
+        let keep = 1.0 - self.config.weight_decay;
+        let types = self.neuron_types();
+        let layer_types = self.neuron_layer_types();
+
+        for (from, row) in self.connections.iter_mut().enumerate() {
+            if self.config.freeze_thresholds && layer_types[from] == LayerType::Bias {
+                continue;
+            }
+            for (to, connection) in row.iter_mut().enumerate().filter(|(_, c)| c.connection_enabled && c.trainable) {
+                connection.weight *= keep;
+
+                let sign = types[from].as_weight_factor() * types[to].as_weight_factor();
+                if self.config.limit_weight(connection, sign) {
+                    self.stats.clipped_updates += 1;
+                }
+            }
+        }
+    }
+
+    /// Per-output thresholding, or an argmax match against one-hot targets for a softmax output layer
+    fn is_misclassified(&self, outputs: &[f64], targets: &[f64]) -> bool {
+        match self.config.output_layer {
+            OutputActivation::PerNeuron => outputs
+                .iter()
+                .zip(targets)
+                .any(|(output, target)| (target - output).abs() > ERROR_COUNT_THRESHOLD),
+            OutputActivation::Softmax => argmax(outputs) != argmax(targets),
+        }
+    }
+
+    /// Add the deltas accumulated in batch mode to the weights and clear the buffer
+    ///
+    /// `train_epoch` and `train_pattern` call this themselves; it is a no-op in online mode.
+    pub fn apply_accumulated_deltas(&mut self) {
+        if self.pending_deltas.is_empty() {
+            return;
+        }
+
+        let types = self.neuron_types();
+        let enforce_sign = self.config.enforce_sign_constraints;
+
+        for (from, (row, deltas)) in self.connections.iter_mut().zip(&mut self.pending_deltas).enumerate() {
+            for (to, (connection, delta)) in row.iter_mut().zip(deltas.iter_mut()).enumerate() {
+                if *delta == 0.0 || !connection.connection_enabled || !connection.trainable {
+                    continue;
+                }
+                connection.apply_weight_delta(std::mem::take(delta));
+
+                let sign = types[from].as_weight_factor() * types[to].as_weight_factor();
+                if self.config.limit_weight(connection, sign) {
+                    self.stats.clipped_updates += 1;
+                }
+                if enforce_sign {
+                    connection.enforce_sign(sign);
+                }
+            }
+        }
+    }
+
+    /// Global learning rate for the next update under `config.schedule`
+    ///
+    /// Derived from the recorded history, so it only changes between epochs.
+    pub fn current_learning_rate(&self) -> f64 {
+        let base = self.config.learning_rate;
+        let history = &self.stats.error_history;
+
+        match self.config.schedule {
+            LearningSchedule::Constant => base,
+            LearningSchedule::AdaptiveLr {
+                increase_factor,
+                decrease_factor,
+                min_lr,
+                max_lr,
+            } => {
+                let previous_rate = self.stats.learning_rate_history.last().copied().unwrap_or(base);
+                // The first epoch has nothing to compare against
+                let factor = match history.len().checked_sub(2).map(|i| (history[i], history[i + 1])) {
+                    Some((before, last)) if last < before => increase_factor,
+                    Some((before, last)) if last > before => decrease_factor,
+                    _ => 1.0,
+                };
+                (previous_rate * factor).clamp(min_lr, max_lr)
+            }
+            LearningSchedule::StepDecay {
+                factor,
+                every,
+            } => base * factor.powf((self.stats.epoch / every.max(1)) as f64),
+            LearningSchedule::ExponentialDecay {
+                gamma,
+            } => base * gamma.powf(self.stats.epoch as f64),
+        }
+    }
+
+    /// Run one epoch of ED learning over `training_data` and record it in `stats`
+    ///
+    /// Returns the summed absolute error of the epoch.
+    pub fn train_epoch(&mut self) -> Result<f64, EdError> {
+        self.config.validate()?;
+        self.check_patterns(&self.training_data)?;
+
+        self.stats.pattern_count = self.training_data.len();
+
+        let mut total_error = 0.0;
+        let mut error_count = 0;
+
+        // Detach the patterns so they can be read while the network mutates
+        let patterns = std::mem::take(&mut self.training_data);
+        let mut order: Vec<usize> = (0..patterns.len()).collect();
+        if self.config.shuffle_patterns {
+            order.shuffle(&mut self.rng);
+        }
+
+        for (position, &index) in order.iter().enumerate() {
+            let (error, misclassified) = self.learn_pattern(&patterns[index]);
+            if let UpdateMode::MiniBatch {
+                size,
+            } = self.config.update_mode
+                && (position + 1) % size == 0
+            {
+                self.apply_accumulated_deltas();
+            }
+
+            total_error += error;
+            if misclassified {
+                error_count += 1;
+            }
+        }
+
+        self.training_data = patterns;
+        self.apply_accumulated_deltas();
+        self.decay_weights();
+        self.stats.learning_rate_history.push(self.current_learning_rate());
+        self.stats.update_epoch(self.stats.epoch + 1, total_error, error_count);
+
+        Ok(total_error)
+    }
+
+    /// Train until the epoch error drops below `config.convergence_threshold` or `max_epochs` have run
+    ///
+    /// Epoch numbering continues across calls, so training can be resumed in chunks.
+    pub fn train(&mut self, max_epochs: usize) -> Result<&LearningStats, EdError> {
+        self.train_with_callback(max_epochs, |_| true)
+    }
+
+    /// `train`, handing the stats to `callback` after every epoch
+    ///
+    /// Returning `false` stops training with `StopReason::Aborted`, e.g. to drive a progress bar or
+    /// cancel a run from a UI.
+    pub fn train_with_callback<F: FnMut(&LearningStats) -> bool>(
+        &mut self,
+        max_epochs: usize,
+        callback: F,
+    ) -> Result<&LearningStats, EdError> {
+        self.stats.converged = false;
+        self.stats.stop_reason = None;
+        SessionCursor::default().advance_with(self, &StoppingCriteria::epochs(max_epochs), usize::MAX, callback)?;
+
+        Ok(&self.stats)
+    }
+
+    /// Train until any of the stopping criteria is met, recording which one in `stats.stop_reason`
+    ///
+    /// `stats.converged` is only set when the target error is reached.
+    pub fn train_until(&mut self, criteria: StoppingCriteria) -> Result<&LearningStats, EdError> {
+        self.stats.converged = false;
+        self.stats.stop_reason = None;
+        SessionCursor::default().advance(self, &criteria, usize::MAX)?;
+
+        Ok(&self.stats)
+    }
+
+    /// Train with early stopping on `validation`, restoring the weights and neuron thresholds of the best validation epoch
+    ///
+    /// Stops once validation error has not improved for `patience` epochs, on convergence, or
+    /// after `max_epochs`. On convergence the final weights are kept, so `stats` still describes
+    /// the model. An empty validation set is rejected.
+    pub fn train_with_validation(
+        &mut self,
+        validation: &[TrainingPattern],
+        patience: usize,
+        max_epochs: usize,
+    ) -> Result<&LearningStats, EdError> {
+        self.train_until(StoppingCriteria::epochs(max_epochs).validation(validation.to_vec(), patience))
+    }
+
+    /// Summed absolute error on `pattern` before and after one `train_pattern` step
+    ///
+    /// A sanity check on the learning direction: a single step should not make the error worse on
+    /// average. The step is kept, stats included.
+    pub fn verify_error_decrease(&mut self, pattern: &TrainingPattern) -> Result<(f64, f64), EdError> {
+        self.check_patterns(std::slice::from_ref(pattern))?;
+        let before = self.validation_error(std::slice::from_ref(pattern))?;
+        self.train_pattern(pattern)?;
+        let after = self.validation_error(std::slice::from_ref(pattern))?;
+
+        Ok((before, after))
+    }
+
+    /// Summed absolute error over `patterns` from forward passes only
+    pub(crate) fn validation_error(&self, patterns: &[TrainingPattern]) -> Result<f64, EdError> {
+        let mut total = 0.0;
+
+        for pattern in patterns {
+            let outputs = self.predict(&pattern.inputs)?;
+            total += outputs
+                .iter()
+                .zip(&pattern.targets)
+                .map(|(output, target)| (target - output).abs())
+                .sum::<f64>();
+        }
+
+        Ok(total)
+    }
+
+    /// Score `patterns` without training, counting a pattern correct when every output is within `threshold` of its target
+    ///
+    /// Unlike `stats`, which tracks the training set, this works on any held-out set. A softmax
+    /// output layer instead counts argmax matches and ignores `threshold`.
+    pub fn evaluate(&self, patterns: &[TrainingPattern], threshold: f64) -> Result<EvalReport, EdError> {
+        self.evaluate_by(patterns, threshold, |inputs| self.predict(inputs))
+    }
+
+    /// `evaluate` through `predict_with_noise`, drawing the weight noise from `rng`
+    pub fn evaluate_with_noise<R: Rng>(&self, patterns: &[TrainingPattern], threshold: f64, rng: &mut R) -> Result<EvalReport, EdError> {
+        self.evaluate_by(patterns, threshold, |inputs| self.predict_with_noise(inputs, rng))
+    }
+
+    fn evaluate_by<F: FnMut(&[f64]) -> Result<Vec<f64>, EdError>>(
+        &self,
+        patterns: &[TrainingPattern],
+        threshold: f64,
+        mut predict: F,
+    ) -> Result<EvalReport, EdError> {
+        self.check_patterns(patterns)?;
+
+        let mut correct = 0;
+        let mut squared_error = 0.0;
+        let mut per_pattern_errors = Vec::with_capacity(patterns.len());
+
+        for pattern in patterns {
+            let outputs = predict(&pattern.inputs)?;
+            let errors: Vec<f64> = outputs
+                .iter()
+                .zip(&pattern.targets)
+                .map(|(output, target)| target - output)
+                .collect();
+
+            let correct_pattern = match self.config.output_layer {
+                OutputActivation::PerNeuron => errors.iter().all(|error| error.abs() <= threshold),
+                OutputActivation::Softmax => argmax(&outputs) == argmax(&pattern.targets),
+            };
+            if correct_pattern {
+                correct += 1;
+            }
+            squared_error += errors.iter().map(|error| error * error).sum::<f64>();
+            per_pattern_errors.push(errors.iter().map(|error| error.abs()).sum());
+        }
+
+        Ok(EvalReport {
+            accuracy: 100.0 * correct as f64 / patterns.len() as f64,
+            mean_squared_error: squared_error / (patterns.len() * self.dimensions.output_size) as f64,
+            per_pattern_errors,
+        })
+    }
+
+    /// Tally predicted against actual classes over `patterns` with forward passes only
+    ///
+    /// Classes are the argmax of outputs and targets; a single-output network is split at 0.5
+    /// into classes 0 and 1.
+    pub fn confusion_matrix(&self, patterns: &[TrainingPattern]) -> Result<ConfusionMatrix, EdError> {
+        self.check_patterns(patterns)?;
+
+        let classes = self.dimensions.output_size.max(2);
+        let class_of = |values: &[f64]| match values {
+            [value] => usize::from(*value >= 0.5),
+            _ => argmax(values).unwrap_or(0),
+        };
+        let mut counts = vec![vec![0; classes]; classes];
+
+        for pattern in patterns {
+            let outputs = self.predict(&pattern.inputs)?;
+            counts[class_of(&pattern.targets)][class_of(&outputs)] += 1;
+        }
+
+        Ok(ConfusionMatrix {
+            counts,
+        })
+    }
+
+    /// Learning statistics accumulated by training so far
+    pub fn stats(&self) -> &LearningStats {
+        &self.stats
+    }
+
+    /// Write the network (weights, config, stats and training data) to `path` as JSON
+    ///
+    /// The update hook is not saved.
+    pub fn save_json<P: AsRef<Path>>(&self, path: P) -> Result<(), EdError> {
+        std::fs::write(path, json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Read a network written by `save_json`, rejecting files whose layers or connection matrix
+    /// disagree with the stored dimensions or that contain non-finite weights
+    pub fn load_json<P: AsRef<Path>>(path: P) -> Result<Self, EdError> {
+        Self::load_json_with(path, false).map(|(network, _)| network)
+    }
+
+    /// `load_json`, optionally repairing non-finite weights instead of rejecting the file
+    ///
+    /// Non-finite weights are written as `null` in JSON and read back as NaN.
+    pub fn load_json_with<P: AsRef<Path>>(path: P, repair: bool) -> Result<(Self, RepairReport), EdError> {
+        let mut network: Self = json::from_str(&std::fs::read_to_string(path)?)?;
+
+        network.check_loaded()?;
+        let report = network.scan_weights(repair)?;
+        Ok((network, report))
+    }
+
+    /// Write the network to `path` in the compact binary format
+    ///
+    /// The file starts with `BINARY_MAGIC` and a little-endian `BINARY_FORMAT_VERSION`.
+    #[cfg(feature = "binary")]
+    pub fn save_bin<P: AsRef<Path>>(&self, path: P) -> Result<(), EdError> {
+        let body = binary::to_bytes(self)?;
+        let mut bytes = Vec::with_capacity(BINARY_MAGIC.len() + 2 + body.len());
+
+        bytes.extend_from_slice(&BINARY_MAGIC);
+        bytes.extend_from_slice(&BINARY_FORMAT_VERSION.to_le_bytes());
+        bytes.extend_from_slice(&body);
+
+        std::fs::write(path, bytes)?;
+        Ok(())
+    }
+
+    /// Read a network written by `save_bin`, rejecting other files, other format versions and
+    /// non-finite weights
+    #[cfg(feature = "binary")]
+    pub fn load_bin<P: AsRef<Path>>(path: P) -> Result<Self, EdError> {
+        Self::load_bin_with(path, false).map(|(network, _)| network)
+    }
+
+    /// `load_bin`, optionally repairing non-finite weights instead of rejecting the file
+    #[cfg(feature = "binary")]
+    pub fn load_bin_with<P: AsRef<Path>>(path: P, repair: bool) -> Result<(Self, RepairReport), EdError> {
+        let path = path.as_ref();
+        let bytes = std::fs::read(path)?;
+
+        if bytes.len() < BINARY_MAGIC.len() + 2 || bytes[..BINARY_MAGIC.len()] != BINARY_MAGIC {
+            return Err(EdError::SerializationError(format!(
+                "{} is not an edla-rs binary model",
+                path.display()
+            )));
+        }
+        let found = u16::from_le_bytes([bytes[4], bytes[5]]);
+        if found != BINARY_FORMAT_VERSION {
+            return Err(EdError::VersionMismatch {
+                expected: BINARY_FORMAT_VERSION,
+                found,
+            });
+        }
+
+        let mut network: Self = binary::from_bytes(&bytes[6..])?;
+        network.check_loaded()?;
+        let report = network.scan_weights(repair)?;
+        Ok((network, report))
+    }
+
+    /// Find non-finite weights in one pass, rejecting them or zeroing and disabling them when `repair` is set
+    pub(crate) fn scan_weights(&mut self, repair: bool) -> Result<RepairReport, EdError> {
+        let mut report = RepairReport::default();
+
+        for (from, row) in self.connections.iter_mut().enumerate() {
+            for (to, connection) in row.iter_mut().enumerate() {
+                if connection.weight.is_finite() {
+                    continue;
+                }
+
+                report.repaired.push((from, to));
+                if repair {
+                    connection.weight = 0.0;
+                    connection.connection_enabled = false;
+                }
+            }
+        }
+
+        if !repair && !report.repaired.is_empty() {
+            let shown: Vec<String> = report
+                .repaired
+                .iter()
+                .take(5)
+                .map(|(from, to)| format!("{}->{}", from, to))
+                .collect();
+            return Err(EdError::NumericalInstability(format!(
+                "{} non-finite weights, first at {}",
+                report.repaired.len(),
+                shown.join(", ")
+            )));
+        }
+
+        Ok(report)
+    }
+
+    /// Check that a deserialized network's layers and connection matrix match its dimensions
+    fn check_loaded(&self) -> Result<(), EdError> {
+        let dimensions = &self.dimensions;
+        let expected = NetworkDimensions::new(dimensions.input_size, dimensions.hidden_size, dimensions.output_size)
+            .with_inhibitory_inputs(dimensions.inhibitory_inputs);
+        if dimensions.total_neurons != expected.total_neurons {
+            return Err(EdError::DimensionMismatch {
+                expected: expected.total_neurons,
+                got: dimensions.total_neurons,
+            });
+        }
+
+        self.check_invariants()
+    }
+
+    /// Learned threshold of neuron `index`: the summed input it receives from the bias layer
+    ///
+    /// Bias-layer connections start within `threshold_init_range` and train like any other
+    /// connection unless `config.freeze_thresholds` is set. `None` for neurons outside the hidden
+    /// and output layers, whose values are clamped. The neuron's own `Neuron::threshold`, enabled by
+    /// `config.neuron_thresholds`, is not included.
+    pub fn threshold(&self, index: usize) -> Option<f64> {
+        let layer = self.neuron_layer_types().get(index).copied()?;
+        if !matches!(layer, LayerType::Hidden | LayerType::Output) {
+            return None;
+        }
+
+        let bias_rows = self.connections.get(self.layer_range(LayerType::Bias))?;
+        Some(
+            bias_rows
+                .iter()
+                .filter_map(|row| row.get(index))
+                .filter(|connection| connection.connection_enabled)
+                .map(|connection| connection.weight * self.config.bias)
+                .sum(),
+        )
+    }
+
+    /// Current output layer activations
+    fn output_values(&self) -> Vec<f64> {
+        self.layers
+            .iter()
+            .filter(|layer| layer.layer_type == LayerType::Output)
+            .flat_map(|layer| layer.neurons.iter().map(|neuron| neuron.output))
+            .collect()
+    }
+
+    /// Validate the full training setup without committing any weight changes
+    ///
+    /// Checks the configuration ranges, the pattern dimensionality, that every output neuron is wired
+    /// to the input layer within `timesteps` recurrent steps, and that one forward + ED learning step
+    /// on the first pattern stays finite. Weights, neuron states, batch-mode pending deltas, `stats`
+    /// and `is_replayable` are restored afterwards.
+    pub fn dry_run(&mut self, patterns: &[TrainingPattern]) -> Result<(), EdError> {
+        self.config.validate()?;
+        self.check_invariants()?;
+        self.check_patterns(patterns)?;
+        self.check_output_reachability()?;
+
+        let snapshot_connections = self.connections.clone();
+        let snapshot_layers = self.layers.clone();
+        let snapshot_rng = self.rng.clone();
+        let snapshot_deltas = self.pending_deltas.clone();
+        let snapshot_hooked = self.trained_with_hook;
+        let snapshot_stats = self.stats.clone();
+
+        let pattern = &patterns[0];
+        self.propagate(&pattern.inputs, self.config.timesteps, Pass::Training);
+        self.diffuse_error(&pattern.targets);
+        self.update_weights();
+
+        let result = self.check_finite();
+
+        self.connections = snapshot_connections;
+        self.layers = snapshot_layers;
+        self.rng = snapshot_rng;
+        self.pending_deltas = snapshot_deltas;
+        self.trained_with_hook = snapshot_hooked;
+        self.stats = snapshot_stats;
+
+        result
+    }
+
+    /// Verify the layers and connection matrix agree with the dimensions and every
+    /// `connections[from][to]` records its own position
+    ///
+    /// Loading and importing run this; hand-built networks should too before training.
+    pub fn check_invariants(&self) -> Result<(), EdError> {
+        self.check_layout()?;
+
+        for (from, row) in self.connections.iter().enumerate() {
+            for (to, connection) in row.iter().enumerate() {
+                if connection.from != from || connection.to != to {
+                    return Err(EdError::InvalidTopology(format!(
+                        "connection at [{}][{}] is labelled {} -> {}",
+                        from, to, connection.from, connection.to
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Disable every enabled connection whose absolute weight is below `threshold`, returning how many were pruned
+    ///
+    /// Pruned connections get weight 0 like those cut at construction, so propagation, learning and
+    /// weight export all skip them.
+    pub fn prune_weak_connections(&mut self, threshold: f64) -> usize {
+        let mut pruned = 0;
+
+        for connection in self.connections.iter_mut().flatten() {
+            if connection.connection_enabled && connection.trainable && connection.weight.abs() < threshold {
+                connection.connection_enabled = false;
+                connection.weight = 0.0;
+                connection.previous_delta = 0.0;
+                pruned += 1;
+            }
+        }
+
+        pruned
+    }
+
+    /// Every connection whose weight has the opposite sign to `from_type.as_weight_factor() * to_type.as_weight_factor()`
+    ///
+    /// Zero weights, including disabled connections, never count as violations, and neither do
+    /// fixed connections such as lateral inhibition, which are negative by design.
+    pub fn validate_sign_constraints(&self) -> Vec<SignViolation> {
+        let types = self.neuron_types();
+        let mut violations = Vec::new();
+
+        for (from, (row, from_type)) in self.connections.iter().zip(&types).enumerate() {
+            for (to, (connection, to_type)) in row.iter().zip(&types).enumerate() {
+                let expected_sign = from_type.as_weight_factor() * to_type.as_weight_factor();
+                if connection.trainable && connection.weight * expected_sign < 0.0 {
+                    violations.push(SignViolation {
+                        from,
+                        to,
+                        weight: connection.weight,
+                        expected_sign,
+                    });
+                }
+            }
+        }
+
+        violations
+    }
+
+    /// Min, max, mean and spread of the enabled weights, plus how many outputs sit at the sigmoid's rails
+    ///
+    /// Saturation reads the outputs left by the last forward pass or training step.
+    pub fn weight_stats(&self) -> WeightStats {
+        let enabled: Vec<f64> = self
+            .connections
+            .iter()
+            .flatten()
+            .filter(|connection| connection.connection_enabled)
+            .map(|connection| connection.weight)
+            .collect();
+        let saturated_outputs = self
+            .layers
+            .iter()
+            .filter(|layer| layer.layer_type == LayerType::Output)
+            .flat_map(|layer| &layer.neurons)
+            .filter(|neuron| neuron.output < 0.01 || neuron.output > 0.99)
+            .count();
+
+        if enabled.is_empty() {
+            return WeightStats {
+                saturated_outputs,
+                ..WeightStats::default()
+            };
+        }
+
+        let count = enabled.len() as f64;
+        let mean = enabled.iter().sum::<f64>() / count;
+        let variance = enabled.iter().map(|weight| (weight - mean).powi(2)).sum::<f64>() / count;
+
+        WeightStats {
+            enabled_connections: enabled.len(),
+            min: enabled.iter().copied().fold(f64::INFINITY, f64::min),
+            max: enabled.iter().copied().fold(f64::NEG_INFINITY, f64::max),
+            mean,
+            std_dev: variance.sqrt(),
+            saturated_outputs,
+        }
+    }
+
+    /// Rewrite every connection's `from`/`to` to match its matrix position, returning how many changed
+    ///
+    /// Learning and propagation index the matrix by position, so this only fixes the labels
+    /// of hand-built data; weights stay where they are.
+    pub fn repair_connection_indices(&mut self) -> usize {
+        let mut repaired = 0;
+
+        for (from, row) in self.connections.iter_mut().enumerate() {
+            for (to, connection) in row.iter_mut().enumerate() {
+                if connection.from != from || connection.to != to {
+                    connection.from = from;
+                    connection.to = to;
+                    repaired += 1;
+                }
+            }
+        }
+
+        repaired
+    }
+
+    /// Verify layers and connection matrix agree with the declared dimensions
+    ///
+    /// Every public entry point that indexes the matrix runs this first, so edits to the public
+    /// fields surface as errors rather than out-of-bounds panics.
+    fn check_layout(&self) -> Result<(), EdError> {
+        let total = self.dimensions.total_neurons;
+        let neuron_count: usize = self.layers.iter().map(|layer| layer.neurons.len()).sum();
+
+        if neuron_count != total {
+            return Err(EdError::DimensionMismatch {
+                expected: total,
+                got: neuron_count,
+            });
+        }
+        let order = self.layers.iter().map(|layer| layer.layer_type);
+        if !order
+            .clone()
+            .eq([LayerType::Bias, LayerType::Input, LayerType::Hidden, LayerType::Output])
+        {
+            return Err(EdError::InvalidTopology(format!(
+                "layers must be ordered bias, input, hidden, output, got {:?}",
+                order.collect::<Vec<_>>()
+            )));
+        }
+        for layer in &self.layers {
+            let expected = match layer.layer_type {
+                LayerType::Bias => 2,
+                LayerType::Input => self.dimensions.input_neurons(),
+                LayerType::Hidden => self.dimensions.hidden_size,
+                LayerType::Output => self.dimensions.output_size,
+            };
+            if layer.neurons.len() != expected {
+                return Err(EdError::DimensionMismatch {
+                    expected,
+                    got: layer.neurons.len(),
+                });
+            }
+        }
+        if self.connections.len() != total {
+            return Err(EdError::DimensionMismatch {
+                expected: total,
+                got: self.connections.len(),
+            });
+        }
+        if let Some(row) = self.connections.iter().find(|row| row.len() != total) {
+            return Err(EdError::DimensionMismatch {
+                expected: total,
+                got: row.len(),
+            });
+        }
+
+        Ok(())
+    }
+
+    /// Verify every pattern matches the network's logical input and output sizes
+    pub(crate) fn check_patterns(&self, patterns: &[TrainingPattern]) -> Result<(), EdError> {
+        self.check_layout()?;
+        if patterns.is_empty() {
+            return Err(EdError::EmptyTrainingSet);
+        }
+
+        for pattern in patterns {
+            if pattern.inputs.len() != self.dimensions.input_size {
+                return Err(EdError::DimensionMismatch {
+                    expected: self.dimensions.input_size,
+                    got: pattern.inputs.len(),
+                });
+            }
+            if pattern.targets.len() != self.dimensions.output_size {
+                return Err(EdError::DimensionMismatch {
+                    expected: self.dimensions.output_size,
+                    got: pattern.targets.len(),
+                });
+            }
+            if pattern.inputs.iter().chain(&pattern.targets).any(|value| !value.is_finite()) {
+                return Err(EdError::NumericalInstability(format!(
+                    "pattern {} contains a non-finite value",
+                    pattern.id
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify every output neuron learns from enabled connections and sees the input layer in time
+    fn check_output_reachability(&self) -> Result<(), EdError> {
+        let total = self.dimensions.total_neurons;
+        let input_range = self.layer_range(LayerType::Input);
+
+        // Hop distance from the input layer over enabled connections (breadth-first)
+        let mut distance = vec![usize::MAX; total];
+        let mut frontier: Vec<usize> = input_range.clone().collect();
+
+        for index in input_range {
+            distance[index] = 0;
+        }
+
+        let mut hops = 0;
+        while !frontier.is_empty() {
+            hops += 1;
+            let mut next = Vec::new();
+
+            for &from in &frontier {
+                for (to, connection) in self.connections[from].iter().enumerate() {
+                    if connection.connection_enabled && distance[to] == usize::MAX {
+                        distance[to] = hops;
+                        next.push(to);
+                    }
+                }
+            }
+
+            frontier = next;
+        }
+
+        for to in self.layer_range(LayerType::Output) {
+            let has_input = (0..total).any(|from| self.connections[from][to].connection_enabled);
+
+            if !has_input {
+                return Err(EdError::InvalidTopology(format!(
+                    "output neuron {} has no enabled incoming connections, so no error can be diffused to it",
+                    to
+                )));
+            }
+            if distance[to] == usize::MAX {
+                return Err(EdError::InvalidTopology(format!(
+                    "output neuron {} is not reachable from the input layer",
+                    to
+                )));
+            }
+            if distance[to] > self.config.timesteps {
+                return Err(EdError::InvalidTopology(format!(
+                    "output neuron {} is {} hops from the input layer but only {} timesteps are simulated",
+                    to, distance[to], self.config.timesteps
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Verify all neuron activations and connection weights are finite
+    fn check_finite(&self) -> Result<(), EdError> {
+        for (index, output) in self.neuron_outputs().iter().enumerate() {
+            if !output.is_finite() {
+                return Err(EdError::NumericalInstability(format!(
+                    "neuron {} produced output {}",
+                    index, output
+                )));
+            }
+        }
+
+        for row in &self.connections {
+            for connection in row {
+                if !connection.weight.is_finite() {
+                    return Err(EdError::NumericalInstability(format!(
+                        "connection {} -> {} has weight {}",
+                        connection.from, connection.to, connection.weight
+                    )));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Global index range of the neurons belonging to a layer type
+    pub fn layer_range(&self, layer_type: LayerType) -> Range<usize> {
+        let mut offset = 0;
+
+        for layer in &self.layers {
+            if layer.layer_type == layer_type {
+                return offset..offset + layer.neurons.len();
+            }
+            offset += layer.neurons.len();
+        }
+
+        offset..offset
+    }
+
+    /// Every neuron paired with its global index, in connection-matrix order
+    pub fn neurons(&self) -> impl Iterator<Item = (usize, &Neuron)> {
+        self.layers.iter().flat_map(|layer| &layer.neurons).enumerate()
+    }
+
+    /// Mutable variant of `neurons`
+    pub fn neurons_mut(&mut self) -> impl Iterator<Item = (usize, &mut Neuron)> {
+        self.layers.iter_mut().flat_map(|layer| &mut layer.neurons).enumerate()
+    }
+
+    /// Global index of the `local`-th neuron of a layer, as used by the connection matrix
+    pub fn global_neuron_index(&self, layer_type: LayerType, local: usize) -> Option<usize> {
+        self.layer_range(layer_type).nth(local)
+    }
+
+    /// Connection from global neuron `from` to `to`, `None` when either index is out of range
+    pub fn connection(&self, from: usize, to: usize) -> Option<&Connection> {
+        self.connections.get(from)?.get(to)
+    }
+
+    /// Mutable connection from global neuron `from` to `to`, `None` when either index is out of range
+    pub fn connection_mut(&mut self, from: usize, to: usize) -> Option<&mut Connection> {
+        self.connections.get_mut(from)?.get_mut(to)
+    }
+
+    /// Snapshot of every neuron's output in global index order
+    fn neuron_outputs(&self) -> Vec<f64> {
+        let mut outputs = Vec::with_capacity(self.dimensions.total_neurons);
+        outputs.extend(
+            self.layers
+                .iter()
+                .flat_map(|layer| layer.neurons.iter().map(|neuron| neuron.output)),
+        );
+
+        outputs
+    }
+
+    /// Snapshot of every neuron's type in global index order
+    fn neuron_types(&self) -> Vec<NeuronType> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.neurons.iter().map(|neuron| neuron.neuron_type))
+            .collect()
+    }
+
+    /// Snapshot of every neuron's layer type in global index order
+    fn neuron_layer_types(&self) -> Vec<LayerType> {
+        self.layers
+            .iter()
+            .flat_map(|layer| layer.neurons.iter().map(|_| layer.layer_type))
+            .collect()
+    }
+
+    /// Clamp a pattern onto the bias and input layers and run `timesteps` recurrent updates
+    ///
+    /// `pass` selects which of the config's training-time perturbations apply.
+    fn propagate(&mut self, inputs: &[f64], timesteps: usize, pass: Pass) {
+        let mut rng = self.rng.clone();
+        let mut buffers = PredictBuffers::default();
+        self.run_recurrent(inputs, timesteps, pass, &mut rng, &mut buffers);
+        self.rng = rng;
+        self.dropout_scales = std::mem::take(&mut buffers.dropout_scales);
+        let states = buffers.outputs.iter().zip(&buffers.sums);
+
+        for (neuron, (&output, &sum)) in self.layers.iter_mut().flat_map(|layer| layer.neurons.iter_mut()).zip(states) {
+            neuron.reset();
+            neuron.input = sum;
+            neuron.output = output;
+        }
+    }
+
+    /// Recurrent dynamics on flat buffers, leaving the network untouched
+    ///
+    /// Leaves every neuron's final output and weighted input in `buffers`, in global index order.
+    /// Clamped bias/input neurons report their clamped value for both. In stochastic mode the spikes
+    /// drawn from `rng` feed the next timestep, but the reported outputs stay continuous. Weight
+    /// noise adds fresh Gaussian noise from `rng` to every weight read. Dropout silences each hidden
+    /// neuron with that probability for every timestep and scales the survivors up to keep the
+    /// expected activity. `pass` decides which of these apply.
+    #[deny(clippy::indexing_slicing)]
+    fn run_recurrent<R: Rng>(&self, inputs: &[f64], timesteps: usize, pass: Pass, rng: &mut R, buffers: &mut PredictBuffers) {
+        #[cfg(feature = "testing")]
+        if crate::testing::clone_per_pattern() {
+            std::hint::black_box(self.connections.clone());
+        }
+
+        let (mode, weight_noise) = match pass {
+            Pass::Clean => (NeuronOutputMode::Deterministic, 0.0),
+            Pass::Noisy => (NeuronOutputMode::Deterministic, self.config.weight_noise_std),
+            Pass::Sampled | Pass::Training => (self.config.neuron_output_mode, self.config.weight_noise_std),
+        };
+        let dropout = if pass == Pass::Training {
+            self.config.dropout_rate
+        } else {
+            0.0
+        };
+
+        let PredictBuffers {
+            outputs,
+            sums,
+            previous,
+            dropout_scales,
+        } = buffers;
+        let copies = self.dimensions.input_copies();
+
+        outputs.clear();
+        for layer in &self.layers {
+            for local in 0..layer.neurons.len() {
+                outputs.push(match layer.layer_type {
+                    LayerType::Bias => self.config.bias,
+                    // Neurons 2k and 2k+1 are the inhibitory/excitatory pair for logical input k, or just k unpaired
+                    LayerType::Input => inputs.get(local / copies).copied().unwrap_or(0.0),
+                    LayerType::Hidden | LayerType::Output => 0.0,
+                });
+            }
+        }
+
+        sums.clear();
+        sums.extend_from_slice(outputs);
+        previous.clear();
+        previous.extend_from_slice(outputs);
+        // Only sampled spikes need their own buffer; deterministic outputs feed the next timestep directly
+        let stochastic = mode == NeuronOutputMode::Stochastic;
+        let mut fired = if stochastic {
+            outputs.clone()
+        } else {
+            Vec::new()
+        };
+        // Hidden and output neurons form one contiguous block after the clamped layers
+        let output_range = self.layer_range(LayerType::Output);
+        let computed = self.layer_range(LayerType::Hidden).start..output_range.end;
+        let hidden_activation = self.config.activation_for(LayerType::Hidden);
+        let output_activation = self.config.activation_for(LayerType::Output);
+        // Output scale of each hidden neuron for this pattern, 0.0 when dropped
+        let hidden_range = self.layer_range(LayerType::Hidden);
+        dropout_scales.clear();
+        if dropout > 0.0 {
+            dropout_scales.extend(hidden_range.clone().map(|_| {
+                if rng.random_bool(dropout) {
+                    0.0
+                } else {
+                    1.0 / (1.0 - dropout)
+                }
+            }));
+        }
+
+        for _ in 0..timesteps {
+            // All neurons update synchronously from the previous timestep's outputs
+            previous.copy_from_slice(if stochastic {
+                &fired
+            } else {
+                outputs
+            });
+
+            let neurons = self.layers.iter().flat_map(|layer| &layer.neurons);
+            let states = sums.iter_mut().zip(outputs.iter_mut()).zip(neurons).enumerate();
+            for (to, ((sum, output), neuron)) in states.skip(computed.start).take(computed.len()) {
+                let mut weight_of = |connection: &Connection| {
+                    if weight_noise > 0.0 {
+                        connection.weight + random_gaussian(rng, 0.0, weight_noise)
+                    } else {
+                        connection.weight
+                    }
+                };
+                *sum = previous
+                    .iter()
+                    .zip(&self.connections)
+                    .filter_map(|(output, row)| row.get(to).filter(|c| c.connection_enabled).map(|c| weight_of(c) * output))
+                    .sum::<f64>()
+                    - neuron.threshold;
+                let activation = if output_range.contains(&to) {
+                    output_activation
+                } else {
+                    hidden_activation
+                };
+                *output = activation.apply(*sum, self.config.sigmoid_steepness);
+                if let Some(scale) = to.checked_sub(hidden_range.start).and_then(|hidden| dropout_scales.get(hidden)) {
+                    *output *= scale;
+                }
+                if let Some(spike) = fired.get_mut(to) {
+                    *spike = mode.fire(*output, rng);
+                }
+            }
+        }
+
+        if self.config.output_layer == OutputActivation::Softmax
+            && let Some(output_sums) = sums.get(output_range.clone())
+        {
+            let scale = 2.0 / self.config.sigmoid_steepness;
+            let logits: Vec<f64> = output_sums.iter().map(|sum| sum * scale).collect();
+            for (output, probability) in outputs.iter_mut().skip(output_range.start).zip(softmax(&logits)) {
+                *output = probability;
+            }
+        }
+    }
+
+    /// Seed output error channels from the configured loss gradient and broadcast them to the hidden layer
+    ///
+    /// Returns the summed absolute prediction error over all outputs.
+    fn diffuse_error(&mut self, targets: &[f64]) -> f64 {
+        let output_range = self.layer_range(LayerType::Output);
+        let hidden_range = self.layer_range(LayerType::Hidden);
+        let mut total_error = 0.0;
+        let mut output_channels = Vec::with_capacity(targets.len());
+        let loss = self.config.loss;
+        let split = self.config.error_split;
+
+        for layer in &mut self.layers {
+            if layer.layer_type == LayerType::Output {
+                for (neuron, target) in layer.neurons.iter_mut().zip(targets) {
+                    // Stats keep the raw error whatever the loss
+                    total_error += (target - neuron.output).abs();
+                    neuron.error_channels = split.split(loss.gradient(neuron.output, *target));
+                    output_channels.push(neuron.error_channels);
+                }
+            }
+        }
+
+        // Every hidden neuron receives the same amplified signal from each output it feeds
+        let amplification = self.config.error_amplification;
+
+        for layer in &mut self.layers {
+            if layer.layer_type == LayerType::Hidden {
+                for (local, neuron) in layer.neurons.iter_mut().enumerate() {
+                    let from = hidden_range.start + local;
+                    let mut channels = ErrorChannels::default();
+
+                    for (to, output) in output_range.clone().zip(&output_channels) {
+                        if self.connections[from][to].connection_enabled {
+                            channels.excitatory += output.excitatory * amplification;
+                            channels.inhibitory += output.inhibitory * amplification;
+                        }
+                    }
+
+                    neuron.error_channels = channels;
+                }
+            }
+        }
+
+        total_error
+    }
+
+    /// Apply the ED weight rule to every enabled connection feeding a hidden or output neuron
+    fn update_weights(&mut self) {
+        let outputs = self.neuron_outputs();
+        let types = self.neuron_types();
+        let layer_types = self.neuron_layer_types();
+        let learning_rate = self.current_learning_rate();
+        let learning_rates: Vec<f64> = layer_types
+            .iter()
+            .map(|&layer| self.config.layer_learning_rates.for_layer(layer).unwrap_or(learning_rate))
+            .collect();
+        let bidirectional = self.config.mode_weight_decrement;
+        let momentum = self.config.momentum;
+        let freeze_thresholds = self.config.freeze_thresholds;
+        let enforce_sign = self.config.enforce_sign_constraints;
+        let epoch = self.stats.epoch;
+        let batch = self.config.update_mode != UpdateMode::Online;
+        if batch && self.pending_deltas.len() != self.connections.len() {
+            self.pending_deltas = vec![vec![0.0; self.connections.len()]; self.connections.len()];
+        }
+        let hidden_start = self.layer_range(LayerType::Hidden).start;
+        let dropout_scales = &self.dropout_scales;
+        let scale_of = |index: usize| {
+            index
+                .checked_sub(hidden_start)
+                .and_then(|hidden| dropout_scales.get(hidden))
+                .copied()
+        };
+        let mut hook = self.update_hook.as_mut();
+        let mut offset = 0;
+
+        self.trained_with_hook |= hook.is_some();
+
+        for layer in &self.layers {
+            if matches!(layer.layer_type, LayerType::Hidden | LayerType::Output) {
+                for (local, neuron) in layer.neurons.iter().enumerate() {
+                    let to = offset + local;
+                    let derivative = self.config.dropout_derivative(layer.layer_type, neuron.output, scale_of(to));
+
+                    for (from, row) in self.connections.iter_mut().enumerate() {
+                        let connection = &mut row[to];
+                        if !connection.connection_enabled
+                            || !connection.trainable
+                            || (freeze_thresholds && layer_types[from] == LayerType::Bias)
+                        {
+                            continue;
+                        }
+
+                        let delta_base = learning_rates[from] * outputs[from] * derivative;
+                        let channels = neuron.error_channels;
+
+                        let (error_signal, delta) = if bidirectional {
+                            let signal = channels.excitatory - channels.inhibitory;
+                            (
+                                signal,
+                                Connection::bidirectional_weight_delta(delta_base, channels, neuron.neuron_type),
+                            )
+                        } else {
+                            // Excitatory sources learn from the excitatory channel, inhibitory from the inhibitory one
+                            let signal = match types[from] {
+                                NeuronType::Excitatory => channels.excitatory,
+                                NeuronType::Inhibitory => channels.inhibitory,
+                            };
+                            (
+                                signal,
+                                Connection::ed_weight_delta(delta_base, signal, types[from], neuron.neuron_type),
+                            )
+                        };
+
+                        let delta = match hook.as_deref_mut() {
+                            Some(hook) => hook.adjust_delta(UpdateContext {
+                                from,
+                                to,
+                                proposed_delta: delta,
+                                weight: connection.weight,
+                                from_type: types[from],
+                                to_type: neuron.neuron_type,
+                                from_layer: layer_types[from],
+                                to_layer: layer.layer_type,
+                                error_signal,
+                                epoch,
+                            }),
+                            None => delta,
+                        };
+                        let delta = connection.momentum_delta(delta, momentum);
+
+                        if batch {
+                            // Both rules only read the pattern's error channels, so summing them stays valid in decrement mode
+                            self.pending_deltas[from][to] += delta;
+                        } else {
+                            connection.apply_weight_delta(delta);
+
+                            let sign = types[from].as_weight_factor() * neuron.neuron_type.as_weight_factor();
+                            if self.config.limit_weight(connection, sign) {
+                                self.stats.clipped_updates += 1;
+                            }
+                            if enforce_sign {
+                                connection.enforce_sign(sign);
+                            }
+                        }
+                    }
+                }
+            }
+            offset += layer.neurons.len();
+        }
+
+        if self.config.neuron_thresholds && !freeze_thresholds {
+            // A threshold acts like a weight from a constant -1 input, so it falls when the output should rise
+            let rate = self.config.layer_learning_rates.bias.unwrap_or(learning_rate);
+            let neurons = self.layers.iter_mut().flat_map(|layer| {
+                let layer_type = layer.layer_type;
+                layer.neurons.iter_mut().map(move |neuron| (layer_type, neuron))
+            });
+            for (index, (layer_type, neuron)) in neurons.enumerate() {
+                if matches!(layer_type, LayerType::Hidden | LayerType::Output) {
+                    let derivative = self.config.dropout_derivative(layer_type, neuron.output, scale_of(index));
+                    let signal = neuron.error_channels.excitatory - neuron.error_channels.inhibitory;
+                    neuron.threshold -= rate * derivative * signal;
+                }
+            }
+        }
+    }
+}
+
+/// Fluent constructor for `EDNetwork`, falling back to `NetworkConfig::default()` for unset parameters
+#[derive(Debug, Clone, Default)]
+pub struct EDNetworkBuilder {
+    pub(crate) input_size: usize,
+    pub(crate) hidden_size: usize,
+    pub(crate) output_size: usize,
+    pub(crate) config: NetworkConfig,
+    pub(crate) training_data: Vec<TrainingPattern>,
+    pub(crate) seed: Option<u64>,
+}
+
+/// Shorter name for `EDNetworkBuilder`, matching `NetworkConfig` and `NetworkLayer`
+pub type NetworkBuilder = EDNetworkBuilder;
+
+impl EDNetworkBuilder {
+    /// Create new builder with empty layers and default configuration
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Number of logical inputs (doubled into excitatory/inhibitory pairs)
+    pub fn inputs(mut self, size: usize) -> Self {
+        self.input_size = size;
+        self
+    }
+
+    /// Number of hidden neurons
+    pub fn hidden(mut self, size: usize) -> Self {
+        self.hidden_size = size;
+        self
+    }
+
+    /// Number of output neurons
+    pub fn outputs(mut self, size: usize) -> Self {
+        self.output_size = size;
+        self
+    }
+
+    /// Replace the whole configuration (later setters still override individual fields)
+    pub fn config(mut self, config: NetworkConfig) -> Self {
+        self.config = config;
+        self
+    }
+
+    /// Training patterns, validated against the dimensions on `build`
+    pub fn training_data(mut self, patterns: Vec<TrainingPattern>) -> Self {
+        self.training_data = patterns;
+        self
+    }
+
+    /// Learning rate controlling weight update magnitude
+    pub fn learning_rate(mut self, learning_rate: f64) -> Self {
+        self.config.learning_rate = learning_rate;
+        self
+    }
+
+    /// Number of recurrent processing timesteps per forward pass
+    pub fn timesteps(mut self, timesteps: usize) -> Self {
+        self.config.timesteps = timesteps;
+        self
+    }
+
+    /// Global bias input applied to all neurons
+    pub fn bias(mut self, bias: f64) -> Self {
+        self.config.bias = bias;
+        self
+    }
+
+    /// Sigmoid activation function steepness parameter
+    pub fn sigmoid_steepness(mut self, steepness: f64) -> Self {
+        self.config.sigmoid_steepness = steepness;
+        self
+    }
+
+    /// Error amplification factor for hidden layers
+    pub fn error_amplification(mut self, amplification: f64) -> Self {
+        self.config.error_amplification = amplification;
+        self
+    }
+
+    /// Weight initialization range
+    pub fn weight_init_range(mut self, range: f64) -> Self {
+        self.config.weight_init_range = range;
+        self
+    }
+
+    /// Threshold/bias initialization range
+    pub fn threshold_init_range(mut self, range: f64) -> Self {
+        self.config.threshold_init_range = range;
+        self
+    }
+
+    /// Residual error threshold for convergence detection
+    pub fn convergence_threshold(mut self, threshold: f64) -> Self {
+        self.config.convergence_threshold = threshold;
+        self
+    }
+
+    /// Build through `EDNetwork::new_seeded` so identical builders produce identical networks
+    pub fn seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Validate the dimensions and training data and construct the network
+    pub fn build(self) -> Result<EDNetwork, EdError> {
+        let dimensions = NetworkDimensions::new(self.input_size, self.hidden_size, self.output_size);
+        let mut network = match self.seed {
+            Some(seed) => EDNetwork::new_seeded(dimensions, self.config, seed)?,
+            None => EDNetwork::new(dimensions, self.config)?,
+        };
+
+        if cfg!(debug_assertions) {
+            network.check_invariants()?;
+        }
+
+        if !self.training_data.is_empty() {
+            network.check_patterns(&self.training_data)?;
+            network.stats = LearningStats::new(self.training_data.len());
+            network.training_data = self.training_data;
+        }
+
+        Ok(network)
+    }
+}
+
+/// Fluent constructor for `NetworkConfig` whose setters reject out-of-range values immediately
+#[derive(Debug, Clone, Default)]
+pub struct NetworkConfigBuilder {
+    config: NetworkConfig,
+}
+
+impl NetworkConfigBuilder {
+    /// Start from `NetworkConfig::default()`
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Start from an existing configuration, e.g. a preset, which must itself be valid
+    pub fn from_config(config: NetworkConfig) -> Result<Self, EdError> {
+        config.validate()?;
+        Ok(Self {
+            config,
+        })
+    }
+
+    /// Apply one change and validate, so any error names the field just set
+    fn set(mut self, change: impl FnOnce(&mut NetworkConfig)) -> Result<Self, EdError> {
+        change(&mut self.config);
+        self.config.validate()?;
+        Ok(self)
+    }
+
+    /// Recurrent timesteps per forward pass, at least 1
+    pub fn timesteps(self, timesteps: usize) -> Result<Self, EdError> {
+        self.set(|config| config.timesteps = timesteps)
+    }
+
+    /// Learning rate, positive
+    pub fn learning_rate(self, learning_rate: f64) -> Result<Self, EdError> {
+        self.set(|config| config.learning_rate = learning_rate)
+    }
+
+    /// Global bias input, finite
+    pub fn bias(self, bias: f64) -> Result<Self, EdError> {
+        self.set(|config| config.bias = bias)
+    }
+
+    /// Sigmoid steepness, positive
+    pub fn sigmoid_steepness(self, steepness: f64) -> Result<Self, EdError> {
+        self.set(|config| config.sigmoid_steepness = steepness)
+    }
+
+    /// Hidden layer error amplification, non-negative
+    pub fn error_amplification(self, amplification: f64) -> Result<Self, EdError> {
+        self.set(|config| config.error_amplification = amplification)
+    }
+
+    /// Weight initialization range, non-negative
+    pub fn weight_init_range(self, range: f64) -> Result<Self, EdError> {
+        self.set(|config| config.weight_init_range = range)
+    }
+
+    /// Threshold initialization range, non-negative
+    pub fn threshold_init_range(self, range: f64) -> Result<Self, EdError> {
+        self.set(|config| config.threshold_init_range = range)
+    }
+
+    /// Convergence threshold on the epoch error, non-negative
+    pub fn convergence_threshold(self, threshold: f64) -> Result<Self, EdError> {
+        self.set(|config| config.convergence_threshold = threshold)
+    }
+
+    /// Symmetric weight limit, positive
+    pub fn weight_clamp(self, limit: f64) -> Result<Self, EdError> {
+        self.set(|config| config.weight_clamp = Some(limit))
+    }
+
+    /// Momentum, in `[0, 1)`
+    pub fn momentum(self, momentum: f64) -> Result<Self, EdError> {
+        self.set(|config| config.momentum = momentum)
+    }
+
+    /// Weight decay, in `[0, 1)`
+    pub fn weight_decay(self, decay: f64) -> Result<Self, EdError> {
+        self.set(|config| config.weight_decay = decay)
+    }
+
+    /// Hidden-neuron dropout rate, in `[0, 1)`
+    pub fn dropout_rate(self, rate: f64) -> Result<Self, EdError> {
+        self.set(|config| config.dropout_rate = rate)
+    }
+
+    /// Finished config, not validated again because every setter already did
+    pub fn build(self) -> NetworkConfig {
+        self.config
+    }
 }
@@ -1,8 +1,9 @@
 use std::fmt;
 
+use rand::{Rng, RngExt};
 use serde::{Deserialize, Serialize};
 
-use super::utils::sigmoid;
+use super::utils::{Activation, sigmoid};
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum NeuronType {
@@ -23,7 +24,18 @@ impl NeuronType {
 
     /// Create alternating excitatory/inhibitory pattern
     pub fn from_index(index: usize) -> Self {
-        if (index + 1) % 2 == 0 {
+        if (index + 1).is_multiple_of(2) {
+            NeuronType::Excitatory
+        } else {
+            NeuronType::Inhibitory
+        }
+    }
+
+    /// Excitatory for the first `⌈ratio · size⌉` indices of a layer, inhibitory after
+    pub fn from_ratio(index: usize, size: usize, ratio: f64) -> Self {
+        let excitatory = (ratio * size as f64).ceil() as usize;
+
+        if index < excitatory {
             NeuronType::Excitatory
         } else {
             NeuronType::Inhibitory
@@ -40,6 +52,32 @@ impl fmt::Display for NeuronType {
     }
 }
 
+/// What a hidden or output neuron passes on after activation
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NeuronOutputMode {
+    /// The continuous activation
+    #[default]
+    Deterministic,
+    /// A 0/1 spike fired with probability equal to the (sigmoid) activation
+    Stochastic,
+}
+
+impl NeuronOutputMode {
+    /// Output for an `activation` in `[0, 1]`, drawing from `rng` only in stochastic mode
+    pub fn fire<R: Rng>(self, activation: f64, rng: &mut R) -> f64 {
+        match self {
+            NeuronOutputMode::Deterministic => activation,
+            NeuronOutputMode::Stochastic => {
+                if rng.random::<f64>() < activation {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+        }
+    }
+}
+
 /// Error signal channels for Error Diffusion learning
 #[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
 pub struct ErrorChannels {
@@ -63,6 +101,19 @@ impl ErrorChannels {
         }
     }
 
+    /// Split the error magnitude between both channels by a sigmoid of `error / temperature`
+    ///
+    /// A zero error feeds neither channel; as `temperature` approaches 0 this becomes `from_prediction_error`.
+    pub fn from_prediction_error_soft(error: f64, temperature: f64) -> Self {
+        // sigmoid(x, s) is 1 / (1 + exp(-2x/s)), so a steepness of 2T divides the error by T
+        let share = sigmoid(error, 2.0 * temperature);
+
+        Self {
+            excitatory: error.abs() * share,
+            inhibitory: error.abs() * (1.0 - share),
+        }
+    }
+
     /// Check if any error signal is present
     pub fn has_error_signal(&self) -> bool {
         self.excitatory > 0.0 || self.inhibitory > 0.0
@@ -74,6 +125,28 @@ impl ErrorChannels {
     }
 }
 
+/// How a signed prediction error is divided between the excitatory and inhibitory channels
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum ErrorSplit {
+    /// Positive error goes wholly excitatory, negative wholly inhibitory
+    #[default]
+    Hard,
+    /// Both channels get a graded share, smoothing the switch around zero error
+    Soft { temperature: f64 },
+}
+
+impl ErrorSplit {
+    /// Channels for a signed prediction error
+    pub fn split(self, error: f64) -> ErrorChannels {
+        match self {
+            ErrorSplit::Hard => ErrorChannels::from_prediction_error(error),
+            ErrorSplit::Soft {
+                temperature,
+            } => ErrorChannels::from_prediction_error_soft(error, temperature),
+        }
+    }
+}
+
 impl fmt::Display for ErrorChannels {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "E:{:.4} I:{:.4}", self.excitatory, self.inhibitory)
@@ -93,6 +166,9 @@ pub struct Neuron {
     pub error_channels: ErrorChannels,
     /// Neuron index within the network
     pub index: usize,
+    /// Own learnable threshold subtracted from the weighted input, 0 unless `NetworkConfig::neuron_thresholds` is set
+    #[serde(default)]
+    pub threshold: f64,
 }
 
 impl Neuron {
@@ -104,12 +180,23 @@ impl Neuron {
             output: 0.0,
             error_channels: ErrorChannels::default(),
             index,
+            threshold: 0.0,
         }
     }
 
     /// Apply sigmoid activation function
     pub fn activate(&mut self, steepness: f64) {
-        self.output = sigmoid(self.input, steepness);
+        self.activate_as(Activation::Sigmoid, steepness);
+    }
+
+    /// Apply `activation` to the accumulated input
+    pub fn activate_as(&mut self, activation: Activation, steepness: f64) {
+        self.output = activation.apply(self.input, steepness);
+    }
+
+    /// `activate_as`, then sample a binary output from it in stochastic mode
+    pub fn activate_with<R: Rng>(&mut self, activation: Activation, steepness: f64, mode: NeuronOutputMode, rng: &mut R) {
+        self.output = mode.fire(activation.apply(self.input, steepness), rng);
     }
 
     /// Reset neuron state for new pattern
@@ -141,6 +228,17 @@ pub struct Connection {
     pub weight: f64,
     /// Whether this connection is enabled
     pub connection_enabled: bool,
+    /// Last delta produced by `momentum_delta`, carried into the next update
+    #[serde(default)]
+    pub previous_delta: f64,
+    /// Whether learning may change the weight; fixed connections such as lateral inhibition opt out
+    #[serde(default = "default_trainable")]
+    pub trainable: bool,
+}
+
+/// Connections saved before fixed connections existed were all learned
+fn default_trainable() -> bool {
+    true
 }
 
 impl Connection {
@@ -153,14 +251,94 @@ impl Connection {
             to,
             weight: constrained_weight,
             connection_enabled: true,
+            previous_delta: 0.0,
+            trainable: true,
         }
     }
 
+    /// Enabled connection with a weight no learning rule touches, bypassing the neuron-type sign
+    pub fn fixed(from: usize, to: usize, weight: f64) -> Self {
+        Self {
+            from,
+            to,
+            weight,
+            connection_enabled: true,
+            previous_delta: 0.0,
+            trainable: false,
+        }
+    }
+
+    /// Add `momentum` times the previous delta to `delta` and remember the result for the next update
+    ///
+    /// A momentum of zero returns `delta` unchanged.
+    pub fn momentum_delta(&mut self, delta: f64, momentum: f64) -> f64 {
+        let delta = if momentum == 0.0 {
+            delta
+        } else {
+            delta + momentum * self.previous_delta
+        };
+        self.previous_delta = delta;
+        delta
+    }
+
     /// Update weight using ED learning rule
     pub fn update_ed_weight(&mut self, delta_base: f64, error_signal: f64, from_type: NeuronType, to_type: NeuronType) {
-        if self.connection_enabled {
-            let weight_delta = delta_base * error_signal * from_type.as_weight_factor() * to_type.as_weight_factor();
+        self.apply_weight_delta(Self::ed_weight_delta(delta_base, error_signal, from_type, to_type));
+    }
+
+    /// Update weight using both error channels at once (`mode_weight_decrement`)
+    ///
+    /// The excitatory channel pushes and the inhibitory channel pulls with the target's sign, regardless of the source type.
+    pub fn update_ed_weight_bidirectional(&mut self, delta_base: f64, error_channels: ErrorChannels, to_type: NeuronType) {
+        self.apply_weight_delta(Self::bidirectional_weight_delta(delta_base, error_channels, to_type));
+    }
+
+    /// Weight change prescribed by the ED learning rule
+    pub fn ed_weight_delta(delta_base: f64, error_signal: f64, from_type: NeuronType, to_type: NeuronType) -> f64 {
+        delta_base * error_signal * from_type.as_weight_factor() * to_type.as_weight_factor()
+    }
+
+    /// Weight change prescribed by the bidirectional ED rule
+    pub fn bidirectional_weight_delta(delta_base: f64, error_channels: ErrorChannels, to_type: NeuronType) -> f64 {
+        delta_base * to_type.as_weight_factor() * (error_channels.excitatory - error_channels.inhibitory)
+    }
+
+    /// Clamp the weight into `[-limit, limit]`, whatever the connection's sign
+    pub fn clamp_weight(&mut self, limit: f64) {
+        self.weight = self.weight.clamp(-limit, limit);
+    }
+
+    /// Clamp the weight into `bounds` mirrored onto the connection's constrained `sign`
+    ///
+    /// `bounds` is `(min, max)` for a positive connection; a negative one is held in `[-max, -min]`.
+    /// Returns whether the weight had to be moved.
+    pub fn clamp_to_bounds(&mut self, bounds: (f64, f64), sign: f64) -> bool {
+        let (min, max) = bounds;
+        let (low, high) = if sign < 0.0 {
+            (-max, -min)
+        } else {
+            (min, max)
+        };
+        let clamped = self.weight.clamp(low, high);
+        let clipped = clamped != self.weight;
+
+        self.weight = clamped;
+        clipped
+    }
+
+    /// Zero the weight if its sign contradicts the connection's constrained `sign`, returning whether it did
+    pub fn enforce_sign(&mut self, sign: f64) -> bool {
+        let violated = self.weight * sign < 0.0;
+
+        if violated {
+            self.weight = 0.0;
+        }
+        violated
+    }
 
+    /// Add a precomputed delta to the weight if the connection is enabled
+    pub fn apply_weight_delta(&mut self, weight_delta: f64) {
+        if self.connection_enabled && self.trainable {
             self.weight += weight_delta;
         }
     }
@@ -0,0 +1,372 @@
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use super::error::EdError;
+use super::network::{EDNetwork, LearningStats};
+use super::neuron::Connection;
+use super::training::TrainingPattern;
+
+/// Early stopping on a held-out validation set
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidationCriterion {
+    /// Patterns evaluated after every epoch without updating weights
+    pub patterns: Vec<TrainingPattern>,
+    /// Epochs without a new best validation error before stopping
+    pub patience: usize,
+}
+
+/// Early stopping once the training error stalls
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct EarlyStopping {
+    /// Epochs without an improvement before stopping
+    pub patience: usize,
+    /// Drop below the best total error that counts as an improvement
+    pub min_delta: f64,
+}
+
+/// Conditions that end a training run, whichever is met first
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StoppingCriteria {
+    /// Epoch budget across the whole run
+    pub max_epochs: usize,
+    /// Total epoch error to reach, `config.convergence_threshold` when `None`
+    pub target_error: Option<f64>,
+    /// Accuracy percentage to reach
+    pub target_accuracy: Option<f64>,
+    /// Wall-clock budget, checked between epochs
+    pub max_duration: Option<Duration>,
+    /// Stop when validation error stops improving; the best weights and thresholds are restored on any
+    /// stop other than reaching `target_error` or `target_accuracy`
+    pub validation: Option<ValidationCriterion>,
+    /// Stop when the training error stops improving
+    #[serde(default)]
+    pub early_stopping: Option<EarlyStopping>,
+}
+
+impl StoppingCriteria {
+    /// Stop on convergence or after `max_epochs`, as `EDNetwork::train` does
+    pub fn epochs(max_epochs: usize) -> Self {
+        Self {
+            max_epochs,
+            target_error: None,
+            target_accuracy: None,
+            max_duration: None,
+            validation: None,
+            early_stopping: None,
+        }
+    }
+
+    pub fn target_error(mut self, error: f64) -> Self {
+        self.target_error = Some(error);
+        self
+    }
+
+    pub fn target_accuracy(mut self, accuracy: f64) -> Self {
+        self.target_accuracy = Some(accuracy);
+        self
+    }
+
+    pub fn max_duration(mut self, duration: Duration) -> Self {
+        self.max_duration = Some(duration);
+        self
+    }
+
+    pub fn validation(mut self, patterns: Vec<TrainingPattern>, patience: usize) -> Self {
+        self.validation = Some(ValidationCriterion {
+            patterns,
+            patience,
+        });
+        self
+    }
+
+    pub fn early_stopping(mut self, patience: usize, min_delta: f64) -> Self {
+        self.early_stopping = Some(EarlyStopping {
+            patience,
+            min_delta,
+        });
+        self
+    }
+}
+
+impl Default for StoppingCriteria {
+    fn default() -> Self {
+        Self::epochs(1000)
+    }
+}
+
+/// Criterion that ended a training run
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum StopReason {
+    /// Epoch error dropped below the target error
+    Converged,
+    /// Accuracy reached `target_accuracy`
+    TargetAccuracy,
+    /// `max_epochs` ran
+    MaxEpochs,
+    /// `max_duration` elapsed
+    Timeout,
+    /// Validation error did not improve for `patience` epochs
+    ValidationPlateau,
+    /// Training error did not improve by `min_delta` for `patience` epochs
+    EarlyStop,
+    /// The per-epoch callback of `EDNetwork::train_with_callback` returned `false`
+    Aborted,
+}
+
+/// Options of a training session
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct TrainingOptions {
+    /// When the session stops, counted across every `advance` call
+    pub stopping: StoppingCriteria,
+}
+
+/// Where a training session stands after an `advance`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum SessionStatus {
+    /// No stopping criterion met yet
+    #[default]
+    Running,
+    /// Stopped for good by the given criterion
+    Stopped(StopReason),
+}
+
+/// Summary returned when a session is finished
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct TrainingOutcome {
+    pub status: SessionStatus,
+    /// Epochs run by the session
+    pub epochs: usize,
+    /// Summed absolute error of the last epoch
+    pub final_error: f64,
+}
+
+/// Progress of a training loop, shared by `TrainingSession` and the `EDNetwork::train*` methods
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub(crate) struct SessionCursor {
+    epochs_run: usize,
+    /// Training time spent in earlier `advance` calls
+    elapsed: Duration,
+    status: SessionStatus,
+    /// Weights of the best validation epoch seen so far
+    best: Option<(f64, Vec<Vec<Connection>>)>,
+    /// Every neuron's own `Neuron::threshold` at the best validation epoch, in global index order
+    #[serde(default)]
+    best_thresholds: Vec<f64>,
+    epochs_since_best: usize,
+    /// Lowest training error seen and the epochs since it improved, for early stopping
+    #[serde(default)]
+    best_training_error: Option<f64>,
+    #[serde(default)]
+    epochs_since_improvement: usize,
+}
+
+impl SessionCursor {
+    /// Run up to `epochs` more epochs, stopping as soon as one of `criteria` is met
+    ///
+    /// The stop reason is also recorded in `network.stats`.
+    pub(crate) fn advance(
+        &mut self,
+        network: &mut EDNetwork,
+        criteria: &StoppingCriteria,
+        epochs: usize,
+    ) -> Result<SessionStatus, EdError> {
+        self.advance_with(network, criteria, epochs, |_| true)
+    }
+
+    /// `advance`, calling `callback` with the stats after every epoch and aborting once it returns `false`
+    pub(crate) fn advance_with<F: FnMut(&LearningStats) -> bool>(
+        &mut self,
+        network: &mut EDNetwork,
+        criteria: &StoppingCriteria,
+        epochs: usize,
+        mut callback: F,
+    ) -> Result<SessionStatus, EdError> {
+        let target_error = criteria.target_error.unwrap_or(network.config.convergence_threshold);
+        let mut remaining = epochs;
+
+        if let Some(validation) = &criteria.validation {
+            if validation.patience == 0 {
+                return Err(EdError::InvalidConfig("validation patience must be at least 1".into()));
+            }
+            network.check_patterns(&validation.patterns)?;
+        }
+        if let Some(early) = criteria.early_stopping
+            && !(early.patience > 0 && early.min_delta.is_finite() && early.min_delta >= 0.0)
+        {
+            return Err(EdError::InvalidConfig(format!(
+                "early stopping needs patience >= 1 and a non-negative min_delta, got {} and {}",
+                early.patience, early.min_delta
+            )));
+        }
+
+        while self.status == SessionStatus::Running {
+            let reason = if self.epochs_run >= criteria.max_epochs {
+                Some(StopReason::MaxEpochs)
+            } else if criteria.max_duration.is_some_and(|limit| self.elapsed >= limit) {
+                Some(StopReason::Timeout)
+            } else {
+                None
+            };
+            if let Some(reason) = reason {
+                self.stop(network, reason);
+                break;
+            }
+            if remaining == 0 {
+                break;
+            }
+
+            let started = Instant::now();
+            network.train_epoch()?;
+            self.elapsed += started.elapsed();
+            self.epochs_run += 1;
+            remaining -= 1;
+
+            if let Some(validation) = &criteria.validation {
+                self.track_validation(network, &validation.patterns)?;
+            }
+            if let Some(early) = criteria.early_stopping {
+                self.track_training_error(network.stats.total_error, early.min_delta);
+            }
+
+            if network.stats.check_convergence(target_error) {
+                self.stop(network, StopReason::Converged);
+            } else if criteria.target_accuracy.is_some_and(|target| network.stats.accuracy >= target) {
+                self.stop(network, StopReason::TargetAccuracy);
+            } else if criteria.validation.as_ref().is_some_and(|v| self.epochs_since_best >= v.patience) {
+                self.stop(network, StopReason::ValidationPlateau);
+            } else if criteria
+                .early_stopping
+                .is_some_and(|early| self.epochs_since_improvement >= early.patience)
+            {
+                self.stop(network, StopReason::EarlyStop);
+            }
+            if !callback(&network.stats) && self.status == SessionStatus::Running {
+                self.stop(network, StopReason::Aborted);
+            }
+        }
+
+        Ok(self.status)
+    }
+
+    /// Record the epoch's validation error and snapshot the weights on a new best
+    fn track_validation(&mut self, network: &mut EDNetwork, patterns: &[TrainingPattern]) -> Result<(), EdError> {
+        let error = network.validation_error(patterns)?;
+        network.stats.validation_history.push(error);
+
+        if self.best.as_ref().is_none_or(|(best, _)| error < *best) {
+            self.best = Some((error, network.connections.clone()));
+            self.best_thresholds = network
+                .layers
+                .iter()
+                .flat_map(|layer| &layer.neurons)
+                .map(|neuron| neuron.threshold)
+                .collect();
+            self.epochs_since_best = 0;
+            network.stats.best_epoch = Some(network.stats.epoch);
+        } else {
+            self.epochs_since_best += 1;
+        }
+
+        Ok(())
+    }
+
+    /// Count epochs whose training error did not beat the best one by more than `min_delta`
+    fn track_training_error(&mut self, error: f64, min_delta: f64) {
+        if self.best_training_error.is_none_or(|best| error < best - min_delta) {
+            self.best_training_error = Some(error);
+            self.epochs_since_improvement = 0;
+        } else {
+            self.epochs_since_improvement += 1;
+        }
+    }
+
+    fn stop(&mut self, network: &mut EDNetwork, reason: StopReason) {
+        self.status = SessionStatus::Stopped(reason);
+        network.stats.stop_reason = Some(reason);
+
+        // A reached target describes the current weights, which `stats` must keep matching
+        let best = self.best.take();
+        let thresholds = std::mem::take(&mut self.best_thresholds);
+        if matches!(reason, StopReason::Converged | StopReason::TargetAccuracy) {
+            return;
+        }
+
+        if let Some((_, connections)) = best {
+            network.connections = connections;
+            let neurons = network.layers.iter_mut().flat_map(|layer| &mut layer.neurons);
+            for (neuron, threshold) in neurons.zip(thresholds) {
+                neuron.threshold = threshold;
+            }
+        }
+    }
+}
+
+/// Resumable training loop that hands control back to the caller between chunks of epochs
+///
+/// The whole session serializes, so it can be saved between `advance` calls and
+/// continued later with identical results.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TrainingSession {
+    network: EDNetwork,
+    options: TrainingOptions,
+    cursor: SessionCursor,
+}
+
+impl TrainingSession {
+    /// Start a session training `network` on its `training_data`
+    pub fn new(mut network: EDNetwork, options: TrainingOptions) -> Self {
+        network.stats.converged = false;
+        network.stats.stop_reason = None;
+
+        Self {
+            network,
+            options,
+            cursor: SessionCursor::default(),
+        }
+    }
+
+    /// Run up to `epochs` more epochs and return control
+    pub fn advance(&mut self, epochs: usize) -> Result<SessionStatus, EdError> {
+        self.cursor.advance(&mut self.network, &self.options.stopping, epochs)
+    }
+
+    /// Current status without advancing
+    pub fn status(&self) -> SessionStatus {
+        self.cursor.status
+    }
+
+    /// Epochs run so far by this session
+    pub fn epochs_run(&self) -> usize {
+        self.cursor.epochs_run
+    }
+
+    /// Learning statistics of the trained network
+    pub fn stats(&self) -> &LearningStats {
+        &self.network.stats
+    }
+
+    pub fn options(&self) -> &TrainingOptions {
+        &self.options
+    }
+
+    pub fn network(&self) -> &EDNetwork {
+        &self.network
+    }
+
+    /// Mutable access between advances, e.g. to adjust the config or training data
+    pub fn network_mut(&mut self) -> &mut EDNetwork {
+        &mut self.network
+    }
+
+    /// End the session and return the trained network
+    pub fn finish(self) -> (EDNetwork, TrainingOutcome) {
+        let outcome = TrainingOutcome {
+            status: self.cursor.status,
+            epochs: self.cursor.epochs_run,
+            final_error: self.network.stats.total_error,
+        };
+
+        (self.network, outcome)
+    }
+}
@@ -1,5 +1,22 @@
+use std::borrow::Cow;
+use std::path::Path;
+
+use rand::Rng;
+use rand::seq::SliceRandom;
 use serde::{Deserialize, Serialize};
 
+use super::MAX_NETWORK_SIZE;
+use super::error::EdError;
+use super::json;
+
+pub mod loaders;
+
+/// Widest operands `TrainingPattern::create_adder_dataset` accepts, giving 65,536 patterns
+pub const MAX_ADDER_BITS: usize = 8;
+/// Largest `TrainingPattern::create_encoder_dataset`: the biggest power of two whose encoder still
+/// fits in `MAX_NETWORK_SIZE` neurons
+pub const MAX_ENCODER_SIZE: usize = 256;
+
 /// Training pattern for ED learning
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TrainingPattern {
@@ -21,6 +38,91 @@ impl TrainingPattern {
         }
     }
 
+    /// Classification pattern whose targets one-hot encode `class_index` among `n_classes`
+    pub fn classification(inputs: Vec<f64>, class_index: usize, n_classes: usize, id: usize) -> Result<Self, EdError> {
+        let targets = one_hot(&[class_index], n_classes)?.into_iter().next().unwrap_or_default();
+        Ok(Self::new(inputs, targets, id))
+    }
+
+    /// Load comma-separated patterns whose first `n_inputs` columns are inputs and remaining columns targets
+    ///
+    /// A first line that does not parse as numbers is taken as a header and skipped. Blank lines are
+    /// ignored, every row must have the same width, and `id` is the 0-based data row index.
+    pub fn from_csv<P: AsRef<Path>>(path: P, n_inputs: usize) -> Result<Vec<Self>, EdError> {
+        let text = std::fs::read_to_string(path)?;
+        let parse = |line: &str| {
+            line.split(',')
+                .map(|field| field.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+        };
+
+        let mut lines = text.lines().enumerate().filter(|(_, line)| !line.trim().is_empty()).peekable();
+        if lines.peek().is_some_and(|(_, line)| parse(line).is_err()) {
+            lines.next();
+        }
+
+        let mut patterns: Vec<Self> = Vec::new();
+        for (number, line) in lines {
+            let values = parse(line).map_err(|_| EdError::SerializationError(format!("invalid number on CSV line {}", number + 1)))?;
+            // The first row fixes the width, which needs at least one target column
+            let width = patterns.first().map(|first| first.inputs.len() + first.targets.len());
+            if values.len() <= n_inputs || width.is_some_and(|width| values.len() != width) {
+                return Err(EdError::DimensionMismatch {
+                    expected: width.unwrap_or(n_inputs + 1),
+                    got: values.len(),
+                });
+            }
+
+            let (inputs, targets) = values.split_at(n_inputs);
+            patterns.push(Self::new(inputs.to_vec(), targets.to_vec(), patterns.len()));
+        }
+
+        if patterns.is_empty() {
+            return Err(EdError::EmptyTrainingSet);
+        }
+
+        Ok(patterns)
+    }
+
+    /// Shuffle `patterns` in place with `rng`; each pattern keeps its `id`
+    ///
+    /// `NetworkConfig::shuffle_patterns` does the same per epoch with the network's own RNG.
+    pub fn shuffle_slice<R: Rng>(patterns: &mut [TrainingPattern], rng: &mut R) {
+        patterns.shuffle(rng);
+    }
+
+    /// Min-max scale every input feature of `patterns` to [0, 1] and return the per-feature `(min, max)`
+    ///
+    /// Feed the returned parameters to `apply_normalization` to transform inference inputs the same
+    /// way. Features are counted from the first pattern, and a feature constant across all patterns
+    /// maps to 0.
+    pub fn normalize_inputs(patterns: &mut [Self]) -> Vec<(f64, f64)> {
+        let width = patterns.first().map_or(0, |first| first.inputs.len());
+        let params: Vec<(f64, f64)> = (0..width)
+            .map(|i| column_range(patterns.iter().filter_map(|pattern| pattern.inputs.get(i).copied())))
+            .collect();
+
+        for pattern in patterns.iter_mut() {
+            Self::apply_normalization(&mut pattern.inputs, &params);
+        }
+
+        params
+    }
+
+    /// Scale `inputs` with the `(min, max)` pairs returned by `normalize_inputs`
+    ///
+    /// Values outside the fitted range land outside [0, 1]; inputs beyond `params` are left as is.
+    pub fn apply_normalization(inputs: &mut [f64], params: &[(f64, f64)]) {
+        for (value, &(min, max)) in inputs.iter_mut().zip(params) {
+            let span = max - min;
+            *value = if span > 0.0 {
+                (*value - min) / span
+            } else {
+                0.0
+            };
+        }
+    }
+
     /// Create XOR training dataset
     pub fn create_xor_dataset() -> Vec<Self> {
         vec![
@@ -31,9 +133,49 @@ impl TrainingPattern {
         ]
     }
 
+    /// Create AND training dataset
+    pub fn create_and_dataset() -> Vec<Self> {
+        Self::gate_dataset(|a, b| a && b)
+    }
+
+    /// Create OR training dataset
+    pub fn create_or_dataset() -> Vec<Self> {
+        Self::gate_dataset(|a, b| a || b)
+    }
+
+    /// Create NAND training dataset
+    pub fn create_nand_dataset() -> Vec<Self> {
+        Self::gate_dataset(|a, b| !(a && b))
+    }
+
+    /// Two-input boolean gate over the four input combinations, in the same order as `create_xor_dataset`
+    fn gate_dataset(gate: impl Fn(bool, bool) -> bool) -> Vec<Self> {
+        [(false, false), (true, false), (false, true), (true, true)]
+            .into_iter()
+            .enumerate()
+            .map(|(id, (a, b))| {
+                let bit = |value: bool| {
+                    if value {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                };
+                Self::new(vec![bit(a), bit(b)], vec![bit(gate(a, b))], id)
+            })
+            .collect()
+    }
+
     /// Create N-bit parity dataset
+    ///
+    /// Panics if `n_bits` is `usize::BITS` or more, as `2^n_bits` patterns cannot be counted.
     pub fn create_parity_dataset(n_bits: usize) -> Vec<Self> {
         let mut patterns = Vec::new();
+        assert!(
+            n_bits < usize::BITS as usize,
+            "parity dataset of {} bits cannot be enumerated",
+            n_bits
+        );
         let n_patterns = 1 << n_bits; // 2^n_bits
 
         for i in 0..n_patterns {
@@ -67,4 +209,311 @@ impl TrainingPattern {
 
         patterns
     }
+
+    /// Create N-to-N one-hot encoder dataset
+    ///
+    /// Pattern `k` has input and target both one-hot at `k`. The classic benchmark squeezes it
+    /// through `log2(n)` hidden neurons, so `n` must be a power of two, at most `MAX_ENCODER_SIZE`.
+    pub fn create_encoder_dataset(n: usize) -> Result<Vec<Self>, EdError> {
+        if !n.is_power_of_two() || !(2..=MAX_ENCODER_SIZE).contains(&n) {
+            return Err(EdError::InvalidConfig(format!(
+                "encoder size must be a power of two between 2 and {}, got {}",
+                MAX_ENCODER_SIZE, n
+            )));
+        }
+
+        let one_hot = |k: usize| {
+            (0..n)
+                .map(|i| {
+                    if i == k {
+                        1.0
+                    } else {
+                        0.0
+                    }
+                })
+                .collect::<Vec<f64>>()
+        };
+
+        Ok((0..n).map(|k| Self::new(one_hot(k), one_hot(k), k)).collect())
+    }
+
+    /// Create binary addition dataset over every pair of `bits`-bit operands
+    ///
+    /// Inputs are the bits of `a` followed by the bits of `b`, targets the `bits + 1` bits of `a + b`,
+    /// all least significant bit first as in `create_parity_dataset`. `bits` is at most `MAX_ADDER_BITS`.
+    pub fn create_adder_dataset(bits: usize) -> Result<Vec<Self>, EdError> {
+        if !(1..=MAX_ADDER_BITS).contains(&bits) {
+            return Err(EdError::InvalidConfig(format!(
+                "adder width must be between 1 and {} bits, got {}",
+                MAX_ADDER_BITS, bits
+            )));
+        }
+
+        let to_bits = |value: usize, width: usize| (0..width).map(|bit| ((value >> bit) & 1) as f64).collect::<Vec<f64>>();
+        let operands = 1usize << bits;
+        let mut patterns = Vec::with_capacity(operands * operands);
+
+        for a in 0..operands {
+            for b in 0..operands {
+                let mut inputs = to_bits(a, bits);
+                inputs.extend(to_bits(b, bits));
+                patterns.push(Self::new(inputs, to_bits(a + b, bits + 1), patterns.len()));
+            }
+        }
+
+        Ok(patterns)
+    }
+}
+
+/// One-hot target vector of `n_classes` for each label, rejecting labels of `n_classes` or more
+///
+/// `n_classes` may not exceed `MAX_NETWORK_SIZE`, as no network has more outputs than that.
+/// `utils::argmax_label` reads a label back out of a target or prediction.
+pub fn one_hot(labels: &[usize], n_classes: usize) -> Result<Vec<Vec<f64>>, EdError> {
+    if n_classes > MAX_NETWORK_SIZE {
+        return Err(EdError::InvalidConfig(format!(
+            "{} classes exceed the largest network of {} neurons",
+            n_classes, MAX_NETWORK_SIZE
+        )));
+    }
+
+    labels
+        .iter()
+        .map(|&label| {
+            if label >= n_classes {
+                return Err(EdError::InvalidConfig(format!(
+                    "class index {} is out of range for {} classes",
+                    label, n_classes
+                )));
+            }
+            let mut targets = vec![0.0; n_classes];
+            targets[label] = 1.0;
+            Ok(targets)
+        })
+        .collect()
+}
+
+/// Shuffle `patterns` with `rng` and split off `test_fraction` of them (rounded) as a test set
+///
+/// Returns `(train, test)`; every pattern keeps its `id`. A fraction outside (0, 1) or one that
+/// leaves either side empty is rejected.
+pub fn split<R: Rng>(
+    mut patterns: Vec<TrainingPattern>,
+    test_fraction: f64,
+    rng: &mut R,
+) -> Result<(Vec<TrainingPattern>, Vec<TrainingPattern>), EdError> {
+    if !(test_fraction > 0.0 && test_fraction < 1.0) {
+        return Err(EdError::InvalidConfig(format!(
+            "test_fraction must be in (0, 1), got {}",
+            test_fraction
+        )));
+    }
+    if patterns.is_empty() {
+        return Err(EdError::EmptyTrainingSet);
+    }
+
+    let test_count = (patterns.len() as f64 * test_fraction).round() as usize;
+    if test_count == 0 || test_count == patterns.len() {
+        return Err(EdError::InvalidConfig(format!(
+            "test_fraction {} of {} patterns leaves one side of the split empty",
+            test_fraction,
+            patterns.len()
+        )));
+    }
+
+    TrainingPattern::shuffle_slice(&mut patterns, rng);
+    let test = patterns.split_off(patterns.len() - test_count);
+    Ok((patterns, test))
+}
+
+/// `(min, max)` of one column, shared by `TrainingPattern::normalize_inputs` and `Normalizer`
+fn column_range(values: impl Iterator<Item = f64>) -> (f64, f64) {
+    values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), value| {
+        (min.min(value), max.max(value))
+    })
+}
+
+/// How `Normalizer` rescales each column
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum NormalizationMethod {
+    /// Map the fitted minimum and maximum to 0 and 1
+    #[default]
+    MinMax,
+    /// Subtract the fitted mean and divide by the population standard deviation
+    ZScore,
+}
+
+/// Per-column scaling fitted on a dataset, to apply again to inference inputs
+///
+/// Every column is transformed as `(value - offset) / scale`. A constant column gets scale 1, so
+/// its fitted value maps to 0 instead of NaN. Serializable, to be saved next to the network it
+/// was trained with.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Normalizer {
+    pub method: NormalizationMethod,
+    /// `(offset, scale)` of each input column
+    pub inputs: Vec<(f64, f64)>,
+    /// `(offset, scale)` of each target column, when targets are normalized too
+    pub targets: Option<Vec<(f64, f64)>>,
+}
+
+impl Normalizer {
+    /// Fit `method` to the input columns of `patterns`, and to the target columns when `normalize_targets`
+    ///
+    /// Rejects an empty set and patterns whose shape differs from the first.
+    pub fn fit(patterns: &[TrainingPattern], method: NormalizationMethod, normalize_targets: bool) -> Result<Self, EdError> {
+        let first = patterns.first().ok_or(EdError::EmptyTrainingSet)?;
+        for pattern in patterns {
+            if pattern.inputs.len() != first.inputs.len() {
+                return Err(EdError::DimensionMismatch {
+                    expected: first.inputs.len(),
+                    got: pattern.inputs.len(),
+                });
+            }
+            if pattern.targets.len() != first.targets.len() {
+                return Err(EdError::DimensionMismatch {
+                    expected: first.targets.len(),
+                    got: pattern.targets.len(),
+                });
+            }
+        }
+
+        let fit_columns = |width: usize, column: &dyn Fn(&TrainingPattern, usize) -> f64| {
+            (0..width)
+                .map(|i| Self::fit_column(method, patterns.iter().map(|pattern| column(pattern, i))))
+                .collect::<Vec<(f64, f64)>>()
+        };
+
+        Ok(Self {
+            method,
+            inputs: fit_columns(first.inputs.len(), &|pattern, i| pattern.inputs[i]),
+            targets: normalize_targets.then(|| fit_columns(first.targets.len(), &|pattern, i| pattern.targets[i])),
+        })
+    }
+
+    fn fit_column(method: NormalizationMethod, values: impl Iterator<Item = f64> + Clone) -> (f64, f64) {
+        let (offset, spread) = match method {
+            NormalizationMethod::MinMax => {
+                let (min, max) = column_range(values);
+                (min, max - min)
+            }
+            NormalizationMethod::ZScore => {
+                let count = values.clone().count() as f64;
+                let mean = values.clone().sum::<f64>() / count;
+                let variance = values.map(|value| (value - mean).powi(2)).sum::<f64>() / count;
+                (mean, variance.sqrt())
+            }
+        };
+
+        (
+            offset,
+            if spread > 0.0 {
+                spread
+            } else {
+                1.0
+            },
+        )
+    }
+
+    /// Normalize the inputs, and the targets if fitted, of every pattern in place
+    pub fn transform(&self, patterns: &mut [TrainingPattern]) {
+        for pattern in patterns {
+            Self::apply(&mut pattern.inputs, &self.inputs);
+            if let Some(targets) = &self.targets {
+                Self::apply(&mut pattern.targets, targets);
+            }
+        }
+    }
+
+    /// Normalized copy of one input vector; values beyond the fitted columns are kept as is
+    pub fn transform_input(&self, inputs: &[f64]) -> Vec<f64> {
+        let mut inputs = inputs.to_vec();
+        Self::apply(&mut inputs, &self.inputs);
+        inputs
+    }
+
+    /// Map network outputs back to the original target scale, unchanged when targets were not normalized
+    pub fn inverse_transform_targets(&self, outputs: &[f64]) -> Vec<f64> {
+        let columns = self.targets.as_deref().unwrap_or_default();
+        let mut outputs = outputs.to_vec();
+        for (value, &(offset, scale)) in outputs.iter_mut().zip(columns) {
+            *value = *value * scale + offset;
+        }
+        outputs
+    }
+
+    fn apply(values: &mut [f64], columns: &[(f64, f64)]) {
+        for (value, &(offset, scale)) in values.iter_mut().zip(columns) {
+            *value = (*value - offset) / scale;
+        }
+    }
+}
+
+/// Version of the `save_dataset` envelope, checked by `load_dataset`
+pub const DATASET_FORMAT_VERSION: u16 = 1;
+
+/// What `save_dataset` writes: the patterns with their shared shape
+#[derive(Serialize, Deserialize)]
+struct DatasetFile<'a> {
+    version: u16,
+    input_size: usize,
+    target_size: usize,
+    patterns: Cow<'a, [TrainingPattern]>,
+}
+
+impl DatasetFile<'_> {
+    /// Reject patterns whose input or target count differs from the envelope's
+    fn check_shape(&self) -> Result<(), EdError> {
+        let mismatch = self
+            .patterns
+            .iter()
+            .find(|pattern| pattern.inputs.len() != self.input_size || pattern.targets.len() != self.target_size);
+
+        match mismatch {
+            Some(pattern) => Err(EdError::SerializationError(format!(
+                "pattern {} has {} inputs and {} targets, expected {} and {}",
+                pattern.id,
+                pattern.inputs.len(),
+                pattern.targets.len(),
+                self.input_size,
+                self.target_size
+            ))),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Write `patterns` to `path` as JSON, with a format version and the shared input/target counts
+///
+/// Every pattern must have the shape of the first.
+pub fn save_dataset<P: AsRef<Path>>(path: P, patterns: &[TrainingPattern]) -> Result<(), EdError> {
+    let first = patterns.first().ok_or(EdError::EmptyTrainingSet)?;
+    let file = DatasetFile {
+        version: DATASET_FORMAT_VERSION,
+        input_size: first.inputs.len(),
+        target_size: first.targets.len(),
+        patterns: Cow::Borrowed(patterns),
+    };
+    file.check_shape()?;
+
+    std::fs::write(path, json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// Read a dataset written by `save_dataset`, rejecting other format versions and naming the first
+/// pattern whose shape disagrees with the stored one
+pub fn load_dataset<P: AsRef<Path>>(path: P) -> Result<Vec<TrainingPattern>, EdError> {
+    let file: DatasetFile = json::from_str(&std::fs::read_to_string(path)?)?;
+    if file.version != DATASET_FORMAT_VERSION {
+        return Err(EdError::VersionMismatch {
+            expected: DATASET_FORMAT_VERSION,
+            found: file.version,
+        });
+    }
+    file.check_shape()?;
+    if file.patterns.is_empty() {
+        return Err(EdError::EmptyTrainingSet);
+    }
+
+    Ok(file.patterns.into_owned())
 }
@@ -0,0 +1,232 @@
+//! Readers for standard dataset file formats
+
+use std::path::Path;
+
+use super::TrainingPattern;
+use crate::core::error::EdError;
+
+/// IDX element type code for unsigned bytes, the only one the MNIST files use
+const IDX_UNSIGNED_BYTE: u8 = 0x08;
+/// Classes of a one-hot encoded IDX label
+const IDX_CLASSES: usize = 10;
+const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// How libsvm labels become pattern targets
+#[derive(Debug, Clone, PartialEq)]
+pub enum LibsvmLabels {
+    /// A single target: `+1`/`1` become 1.0, `-1`/`0` become 0.0
+    Binary,
+    /// One-hot targets, one per class in the given order
+    Classes(Vec<i64>),
+}
+
+impl LibsvmLabels {
+    fn targets(&self, label: i64) -> Option<Vec<f64>> {
+        match self {
+            Self::Binary => match label {
+                1 => Some(vec![1.0]),
+                -1 | 0 => Some(vec![0.0]),
+                _ => None,
+            },
+            Self::Classes(classes) => {
+                let class = classes.iter().position(|&class| class == label)?;
+                let mut targets = vec![0.0; classes.len()];
+                targets[class] = 1.0;
+                Some(targets)
+            }
+        }
+    }
+}
+
+/// Load an IDX image file and its label file (the MNIST format) as patterns
+///
+/// Pixels are scaled from 0..=255 to [0, 1] and labels one-hot encoded into 10 targets. `limit`
+/// keeps only the first patterns. Gzip-compressed files (`.gz`) are read with the `gzip` feature.
+pub fn load_idx_images<P: AsRef<Path>, Q: AsRef<Path>>(
+    images_path: P,
+    labels_path: Q,
+    limit: Option<usize>,
+) -> Result<Vec<TrainingPattern>, EdError> {
+    let images = read_maybe_gzip(images_path.as_ref())?;
+    let labels = read_maybe_gzip(labels_path.as_ref())?;
+
+    parse_idx_images(&images, &labels, limit)
+}
+
+/// `load_idx_images` on in-memory, uncompressed IDX contents
+pub fn parse_idx_images(images: &[u8], labels: &[u8], limit: Option<usize>) -> Result<Vec<TrainingPattern>, EdError> {
+    let (image_dims, pixels) = parse_idx(images)?;
+    let (label_dims, labels) = parse_idx(labels)?;
+    if image_dims.len() < 2 || label_dims.len() != 1 {
+        return Err(EdError::SerializationError(format!(
+            "IDX images need at least 2 dimensions and labels exactly 1, got {} and {}",
+            image_dims.len(),
+            label_dims.len()
+        )));
+    }
+    if image_dims[0] != label_dims[0] {
+        return Err(EdError::DimensionMismatch {
+            expected: image_dims[0],
+            got: label_dims[0],
+        });
+    }
+
+    // The leading count may be 0, so the full product `parse_idx` checked does not bound this one
+    let image_size = image_dims[1..]
+        .iter()
+        .try_fold(1usize, |size, &dim| size.checked_mul(dim))
+        .ok_or_else(|| EdError::SerializationError("IDX: image dimensions overflow".into()))?;
+    let count = limit.map_or(image_dims[0], |limit| limit.min(image_dims[0]));
+    let mut patterns = Vec::with_capacity(count);
+
+    for (id, (image, &label)) in pixels.chunks_exact(image_size.max(1)).zip(labels).take(count).enumerate() {
+        let label = usize::from(label);
+        if label >= IDX_CLASSES {
+            return Err(EdError::SerializationError(format!(
+                "IDX label {} of pattern {} is not a digit",
+                label, id
+            )));
+        }
+
+        let mut targets = vec![0.0; IDX_CLASSES];
+        targets[label] = 1.0;
+        patterns.push(TrainingPattern::new(
+            image.iter().map(|&pixel| f64::from(pixel) / 255.0).collect(),
+            targets,
+            id,
+        ));
+    }
+
+    if patterns.is_empty() {
+        return Err(EdError::EmptyTrainingSet);
+    }
+
+    Ok(patterns)
+}
+
+/// Load a libsvm/SVMlight file (`label index:value ...`) as dense patterns
+///
+/// Indices are 1-based and features missing from a line are 0.0. With `features` as `None` the
+/// width is the largest index in the file. `#` comments and SVMlight `qid:` tokens are ignored.
+pub fn load_libsvm<P: AsRef<Path>>(path: P, features: Option<usize>, labels: &LibsvmLabels) -> Result<Vec<TrainingPattern>, EdError> {
+    parse_libsvm(&std::fs::read_to_string(path)?, features, labels)
+}
+
+/// `load_libsvm` on in-memory file contents
+pub fn parse_libsvm(text: &str, features: Option<usize>, labels: &LibsvmLabels) -> Result<Vec<TrainingPattern>, EdError> {
+    let mut rows = Vec::new();
+
+    for (number, line) in text.lines().enumerate() {
+        let invalid = |msg: String| EdError::SerializationError(format!("libsvm line {}: {}", number + 1, msg));
+        let line = line.split('#').next().unwrap_or_default();
+        let mut tokens = line.split_whitespace();
+        let Some(label) = tokens.next() else {
+            continue;
+        };
+
+        let label = label
+            .parse::<f64>()
+            .ok()
+            .filter(|label| label.fract() == 0.0)
+            .ok_or_else(|| invalid(format!("label {:?} is not an integer", label)))?;
+        let targets = labels
+            .targets(label as i64)
+            .ok_or_else(|| invalid(format!("label {} is not one of {:?}", label, labels)))?;
+
+        let mut values: Vec<(usize, f64)> = Vec::new();
+        for token in tokens.filter(|token| !token.starts_with("qid:")) {
+            let (index, value) = token
+                .split_once(':')
+                .and_then(|(index, value)| Some((index.parse::<usize>().ok()?, value.parse::<f64>().ok()?)))
+                .filter(|&(index, _)| index > 0)
+                .ok_or_else(|| invalid(format!("malformed feature {:?}", token)))?;
+            if let Some(&(previous, _)) = values.last()
+                && index <= previous
+            {
+                let problem = if index == previous {
+                    "duplicate"
+                } else {
+                    "out-of-order"
+                };
+                return Err(invalid(format!("{} feature index {}", problem, index)));
+            }
+            values.push((index, value));
+        }
+
+        rows.push((number, values, targets));
+    }
+
+    let width = match features {
+        Some(width) => width,
+        None => rows
+            .iter()
+            .filter_map(|(_, values, _)| values.last().map(|&(index, _)| index))
+            .max()
+            .unwrap_or(0),
+    };
+
+    let mut patterns = Vec::with_capacity(rows.len());
+    for (number, values, targets) in rows {
+        let mut inputs = vec![0.0; width];
+        for (index, value) in values {
+            *inputs.get_mut(index - 1).ok_or_else(|| {
+                EdError::SerializationError(format!(
+                    "libsvm line {}: feature index {} exceeds {} features",
+                    number + 1,
+                    index,
+                    width
+                ))
+            })? = value;
+        }
+        patterns.push(TrainingPattern::new(inputs, targets, patterns.len()));
+    }
+
+    if patterns.is_empty() {
+        return Err(EdError::EmptyTrainingSet);
+    }
+
+    Ok(patterns)
+}
+
+/// Split an unsigned-byte IDX file into its dimensions and data, checking the data length
+fn parse_idx(bytes: &[u8]) -> Result<(Vec<usize>, &[u8]), EdError> {
+    let invalid = |msg: String| EdError::SerializationError(format!("IDX: {}", msg));
+    let magic = bytes.get(..4).ok_or_else(|| invalid("missing magic number".into()))?;
+    if magic[..2] != [0, 0] || magic[2] != IDX_UNSIGNED_BYTE {
+        return Err(invalid(format!("unsupported magic number {:02x?}", magic)));
+    }
+
+    let rank = usize::from(magic[3]);
+    let header = bytes.get(4..4 + 4 * rank).ok_or_else(|| invalid("truncated dimensions".into()))?;
+    let dims: Vec<usize> = header
+        .chunks_exact(4)
+        .map(|dim| u32::from_be_bytes([dim[0], dim[1], dim[2], dim[3]]) as usize)
+        .collect();
+    let len = dims
+        .iter()
+        .try_fold(1usize, |len, &dim| len.checked_mul(dim))
+        .ok_or_else(|| invalid("dimensions overflow".into()))?;
+
+    let data = &bytes[4 + 4 * rank..];
+    if data.len() != len {
+        return Err(invalid(format!("expected {} data bytes, found {}", len, data.len())));
+    }
+
+    Ok((dims, data))
+}
+
+/// Read a file, inflating it first if it is gzip-compressed
+fn read_maybe_gzip(path: &Path) -> Result<Vec<u8>, EdError> {
+    let bytes = std::fs::read(path)?;
+    if !bytes.starts_with(&GZIP_MAGIC) {
+        return Ok(bytes);
+    }
+
+    #[cfg(feature = "gzip")]
+    return crate::core::gzip::decompress(&bytes);
+    #[cfg(not(feature = "gzip"))]
+    Err(EdError::SerializationError(format!(
+        "{} is gzip-compressed; enable the `gzip` feature or decompress it first",
+        path.display()
+    )))
+}
@@ -1,9 +1,41 @@
-use rand::Rng;
+use rand::{Rng, RngExt};
+use serde::{Deserialize, Serialize};
+
+use super::error::EdError;
+
+/// Distance from 0 and 1 that cross-entropy clamps predictions to
+const CROSS_ENTROPY_EPSILON: f64 = 1e-7;
+
+/// Largest exponent magnitude passed to `exp` by `sigmoid`, keeping its output strictly inside (0, 1)
+const SIGMOID_EXPONENT_LIMIT: f64 = 36.0;
 
 /// Sigmoid activation function with configurable steepness
-/// Formula: 1 / (1 + exp(-2*x/steepness))
+/// Formula: 1 / (1 + exp(-2*x/steepness)), exponent clamped to +/-36 so extreme inputs and zero
+/// steepness stay finite; only a NaN `x` yields NaN
 pub fn sigmoid(x: f64, steepness: f64) -> f64 {
-    1.0 / (1.0 + (-2.0 * x / steepness).exp())
+    let exponent = if x == 0.0 {
+        0.0
+    } else {
+        -2.0 * x / steepness
+    };
+
+    1.0 / (1.0 + exponent.clamp(-SIGMOID_EXPONENT_LIMIT, SIGMOID_EXPONENT_LIMIT).exp())
+}
+
+/// Hyperbolic tangent with the same input scaling as `sigmoid`
+/// Formula: tanh(2*x/steepness), 0 at x = 0 even for zero steepness
+pub fn tanh_activation(x: f64, steepness: f64) -> f64 {
+    if x == 0.0 {
+        return 0.0;
+    }
+
+    (2.0 * x / steepness).tanh()
+}
+
+/// Tanh derivative for weight updates
+/// Formula: 1 - tanh(x)^2
+pub fn tanh_derivative(output: f64) -> f64 {
+    1.0 - output * output
 }
 
 /// Sigmoid derivative for weight updates
@@ -12,7 +44,165 @@ pub fn sigmoid_derivative(output: f64) -> f64 {
     output * (1.0 - output)
 }
 
+/// Normalize values into a probability distribution
+/// Formula: exp(x_i - max) / sum(exp(x_j - max)), the max shift keeps `exp` from overflowing
+pub fn softmax(values: &[f64]) -> Vec<f64> {
+    let max = values.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+    let exps: Vec<f64> = values.iter().map(|value| (value - max).exp()).collect();
+    let sum: f64 = exps.iter().sum();
+
+    exps.iter().map(|value| value / sum).collect()
+}
+
+/// Index of the largest value, the first one on ties
+pub fn argmax(values: &[f64]) -> Option<usize> {
+    values
+        .iter()
+        .enumerate()
+        .fold(None, |best: Option<(usize, f64)>, (i, &value)| match best {
+            Some((_, max)) if value <= max => best,
+            _ => Some((i, value)),
+        })
+        .map(|(i, _)| i)
+}
+
+/// `argmax` as a class label, rejecting empty or non-finite outputs
+pub fn argmax_label(outputs: &[f64]) -> Result<usize, EdError> {
+    if let Some(output) = outputs.iter().find(|output| !output.is_finite()) {
+        return Err(EdError::NumericalInstability(format!("cannot pick a label from output {}", output)));
+    }
+
+    argmax(outputs).ok_or(EdError::DimensionMismatch {
+        expected: 1,
+        got: 0,
+    })
+}
+
 /// Generate random weight within specified range
+/// Range: [0, range), so weights start non-negative before the neuron type factors apply
 pub fn random_weight<R: Rng>(rng: &mut R, range: f64) -> f64 {
     rng.random::<f64>() * range
 }
+
+/// Generate zero-centered random weight
+/// Range: [-range, range)
+pub fn random_weight_symmetric<R: Rng>(rng: &mut R, range: f64) -> f64 {
+    (2.0 * rng.random::<f64>() - 1.0) * range
+}
+
+/// Generate a normally distributed weight with the Box-Muller transform
+pub fn random_gaussian<R: Rng>(rng: &mut R, mean: f64, std: f64) -> f64 {
+    // 1 - u lies in (0, 1], keeping the logarithm finite
+    let radius = (-2.0 * (1.0 - rng.random::<f64>()).ln()).sqrt();
+    let angle = 2.0 * std::f64::consts::PI * rng.random::<f64>();
+
+    mean + std * radius * angle.cos()
+}
+
+/// Distribution of initial base weights and thresholds, before the neuron type factors apply
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WeightInit {
+    /// `[0, range)`, or `[-range, range)` with `symmetric_init`, for weights and thresholds alike
+    Uniform { range: f64 },
+    /// Normal distribution with the given mean and standard deviation
+    Gaussian { mean: f64, std: f64 },
+    /// Uniform with range `1 / sqrt(fan_in)` of the target neuron, so wide layers do not start saturated
+    ScaledByFanIn,
+}
+
+/// Neuron activation function applied to the weighted input sum
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Activation {
+    /// Logistic sigmoid in (0, 1), Kaneko's original choice
+    #[default]
+    Sigmoid,
+    /// Hyperbolic tangent in (-1, 1)
+    Tanh,
+    /// Rectified linear unit
+    ReLU,
+    /// Rectified linear unit with the given slope for negative inputs
+    LeakyReLU(f64),
+    /// Identity, for unbounded regression outputs
+    Linear,
+}
+
+impl Activation {
+    /// Activate a weighted input sum
+    ///
+    /// Sigmoid and tanh scale the input by `2/steepness`, the ReLU variants and linear ignore it.
+    pub fn apply(self, x: f64, steepness: f64) -> f64 {
+        match self {
+            Activation::Sigmoid => sigmoid(x, steepness),
+            Activation::Tanh => tanh_activation(x, steepness),
+            Activation::ReLU => x.max(0.0),
+            Activation::LeakyReLU(slope) => {
+                if x > 0.0 {
+                    x
+                } else {
+                    slope * x
+                }
+            }
+            Activation::Linear => x,
+        }
+    }
+
+    /// Derivative expressed in terms of the activated output, as used by weight updates
+    pub fn derivative(self, output: f64) -> f64 {
+        match self {
+            Activation::Sigmoid => sigmoid_derivative(output),
+            Activation::Tanh => tanh_derivative(output),
+            Activation::ReLU => {
+                if output > 0.0 {
+                    1.0
+                } else {
+                    0.0
+                }
+            }
+            Activation::LeakyReLU(slope) => {
+                if output > 0.0 {
+                    1.0
+                } else {
+                    slope
+                }
+            }
+            Activation::Linear => 1.0,
+        }
+    }
+}
+
+/// How the output layer turns its weighted input sums into outputs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum OutputActivation {
+    /// Each output neuron applies its own `Activation`
+    #[default]
+    PerNeuron,
+    /// Softmax across the output layer after the final timestep, for one-hot multi-class targets
+    ///
+    /// Sums are scaled by `2/steepness` like the sigmoid, so two classes reduce to a sigmoid of their difference.
+    Softmax,
+}
+
+/// Loss whose gradient seeds the output error channels
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+pub enum Loss {
+    /// Squared error `(target - prediction)^2 / 2`, whose gradient is the raw prediction error
+    #[default]
+    Mse,
+    /// Binary cross-entropy `-(t ln p + (1 - t) ln(1 - p))`, for sigmoid or softmax outputs in (0, 1)
+    CrossEntropy,
+}
+
+impl Loss {
+    /// Negative gradient of the loss with respect to `prediction`, signed like `target - prediction`
+    ///
+    /// Cross-entropy clamps the prediction away from 0 and 1 so the gradient stays finite.
+    pub fn gradient(self, prediction: f64, target: f64) -> f64 {
+        match self {
+            Loss::Mse => target - prediction,
+            Loss::CrossEntropy => {
+                let p = prediction.clamp(CROSS_ENTROPY_EPSILON, 1.0 - CROSS_ENTROPY_EPSILON);
+                (target - p) / (p * (1.0 - p))
+            }
+        }
+    }
+}
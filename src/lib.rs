@@ -1 +1,3 @@
 pub mod core;
+#[cfg(feature = "testing")]
+pub mod testing;
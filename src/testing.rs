@@ -0,0 +1,66 @@
+//! Shared helpers for guard tests: an allocation-counting global allocator and regression hooks
+//!
+//! Only built with the `testing` feature.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, Ordering};
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Global allocator wrapper counting allocations made on the current thread
+///
+/// Register it in a test binary with `#[global_allocator] static ALLOC: CountingAllocator = CountingAllocator;`.
+pub struct CountingAllocator;
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        record_allocation();
+        unsafe { System.alloc_zeroed(layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        record_allocation();
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+fn record_allocation() {
+    // try_with: the thread-local may already be torn down while the thread exits
+    let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+}
+
+/// Number of allocations recorded on the current thread so far
+pub fn allocation_count() -> usize {
+    ALLOCATIONS.with(|count| count.get())
+}
+
+/// Run `f` and return its result with the number of allocations it made on this thread
+pub fn count_allocations<T, F: FnOnce() -> T>(f: F) -> (T, usize) {
+    let before = allocation_count();
+    let result = f();
+
+    (result, allocation_count() - before)
+}
+
+static CLONE_PER_PATTERN: AtomicBool = AtomicBool::new(false);
+
+/// Make every forward pass clone the connection matrix, simulating a performance regression
+pub fn inject_clone_per_pattern(enabled: bool) {
+    CLONE_PER_PATTERN.store(enabled, Ordering::SeqCst);
+}
+
+pub(crate) fn clone_per_pattern() -> bool {
+    CLONE_PER_PATTERN.load(Ordering::SeqCst)
+}
@@ -0,0 +1,299 @@
+use std::panic::{self, AssertUnwindSafe};
+
+use edla_rs::core::{
+    INTENTIONAL_PANICS,
+    analysis::{Alignment, MetricSeries, OrderRecorder, PatternCurveRecorder, capacity_report, order_correlation},
+    diagnostics::{FindingCode, preflight},
+    ensemble::EDEnsemble,
+    error::EdError,
+    export::{IndexingConvention, remap},
+    json,
+    network::{EDNetwork, LayerType, NetworkBuilder, NetworkConfig, NetworkDimensions},
+    session::{StoppingCriteria, TrainingOptions, TrainingSession},
+    training::{TrainingPattern, loaders::parse_idx_images, one_hot},
+};
+use rand::{RngExt, SeedableRng, rngs::StdRng};
+
+/// Inputs chosen to hit length checks, non-finite guards and overflow paths
+const ADVERSARIAL: [f64; 7] = [f64::NAN, f64::INFINITY, f64::NEG_INFINITY, f64::MAX, -f64::MAX, 1e300, 0.0];
+
+fn xor_network() -> EDNetwork {
+    NetworkBuilder::new()
+        .inputs(2)
+        .hidden(4)
+        .outputs(1)
+        .seed(1)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap()
+}
+
+fn finite_or_err(result: Result<Vec<f64>, EdError>) {
+    if let Ok(outputs) = result {
+        assert!(outputs.iter().all(|output| output.is_finite()), "{:?}", outputs);
+    }
+}
+
+/// Call every public entry point that reads the network's layout and assert none panics
+fn exercise(network: &mut EDNetwork) {
+    finite_or_err(network.forward(&[0.0, 1.0]));
+    finite_or_err(network.predict(&[0.0, 1.0]));
+    let _ = network.predict_batch(&[vec![1.0, 1.0]]);
+    let _ = network.train_pattern(&TrainingPattern::new(vec![1.0, 0.0], vec![1.0], 0));
+    let _ = network.train_epoch();
+    let _ = network.train(3);
+    let _ = network.train_until(StoppingCriteria::epochs(2).validation(TrainingPattern::create_xor_dataset(), 1));
+    let _ = network.dry_run(&TrainingPattern::create_xor_dataset());
+    let _ = network.check_invariants();
+    let _ = network.export_weights(&network.indexing_convention());
+    let _ = network.import_weights(&[0.0; 4], &network.indexing_convention());
+    let _ = capacity_report(network, &TrainingPattern::create_xor_dataset());
+    let _ = network.stats.to_string();
+}
+
+#[test]
+fn test_construction_rejects_degenerate_sizes_and_configs() {
+    for (inputs, hidden, outputs) in [(0, 1, 1), (1, 0, 1), (1, 1, 0), (usize::MAX, 1, 1), (1, usize::MAX, usize::MAX)] {
+        let built = NetworkBuilder::new().inputs(inputs).hidden(hidden).outputs(outputs).build();
+        assert!(built.is_err(), "{} {} {}", inputs, hidden, outputs);
+    }
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(usize::MAX, usize::MAX, usize::MAX), NetworkConfig::default()),
+        Err(EdError::NetworkTooLarge { .. })
+    ));
+
+    for value in ADVERSARIAL {
+        let configs = [
+            NetworkConfig {
+                learning_rate: value,
+                ..NetworkConfig::default()
+            },
+            NetworkConfig {
+                sigmoid_steepness: value,
+                ..NetworkConfig::default()
+            },
+            NetworkConfig {
+                weight_init_range: value,
+                ..NetworkConfig::default()
+            },
+            NetworkConfig {
+                bias: value,
+                error_amplification: value,
+                ..NetworkConfig::default()
+            },
+        ];
+        for config in configs {
+            if let Ok(mut network) = EDNetwork::new(NetworkDimensions::new(2, 3, 1), config) {
+                network.training_data = TrainingPattern::create_xor_dataset();
+                exercise(&mut network);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_adversarial_inputs_and_patterns() {
+    let mut network = xor_network();
+
+    for inputs in [vec![], vec![0.0], vec![0.0; 3], vec![0.0; 1000]] {
+        assert!(matches!(network.forward(&inputs), Err(EdError::DimensionMismatch { .. })));
+        assert!(matches!(network.predict(&inputs), Err(EdError::DimensionMismatch { .. })));
+    }
+    for value in ADVERSARIAL {
+        finite_or_err(network.forward(&[value, 1.0]));
+        finite_or_err(network.predict(&[1.0, value]));
+        let _ = network.train_pattern(&TrainingPattern::new(vec![value, value], vec![value], usize::MAX));
+        let _ = network.train_pattern(&TrainingPattern::new(vec![1.0, 0.0], vec![value], 7));
+    }
+
+    network.training_data.clear();
+    assert_eq!(network.train_epoch(), Err(EdError::EmptyTrainingSet));
+    assert!(network.train(10).is_err());
+    assert!(network.dry_run(&[]).is_err());
+    network.training_data = vec![TrainingPattern::new(vec![0.0], vec![0.0, 0.0], 0)];
+    assert!(matches!(network.train_epoch(), Err(EdError::DimensionMismatch { .. })));
+
+    network.training_data = vec![TrainingPattern::new(vec![1e300, -1e300], vec![1e300], 0)];
+    for _ in 0..20 {
+        let _ = network.train_epoch();
+    }
+    finite_or_err(network.predict(&[1.0, 1.0]));
+
+    let mut session = TrainingSession::new(
+        xor_network(),
+        TrainingOptions {
+            stopping: StoppingCriteria::epochs(5).validation(Vec::new(), 0),
+        },
+    );
+    assert!(session.advance(5).is_err());
+
+    let _ = preflight(&NetworkBuilder::new(), &[]);
+    let huge = NetworkBuilder::new().inputs(usize::MAX).hidden(4).outputs(usize::MAX);
+    let report = preflight(&huge, &TrainingPattern::create_xor_dataset());
+    assert!(report.codes().contains(&FindingCode::HiddenTooSmall));
+
+    for bits in [20, 31, 32, usize::MAX] {
+        assert!(matches!(
+            TrainingPattern::create_adder_dataset(bits),
+            Err(EdError::InvalidConfig(_))
+        ));
+    }
+    for n in [1 << 20, 1 << 62, usize::MAX] {
+        assert!(matches!(TrainingPattern::create_encoder_dataset(n), Err(EdError::InvalidConfig(_))));
+    }
+    assert!(matches!(one_hot(&[0], usize::MAX), Err(EdError::InvalidConfig(_))));
+    assert!(TrainingPattern::classification(vec![0.0], 0, usize::MAX, 0).is_err());
+}
+
+#[test]
+fn test_edited_public_fields_are_reported_not_panicked() {
+    let edits: Vec<fn(&mut EDNetwork)> = vec![
+        |n| n.connections.truncate(3),
+        |n| n.connections[5].truncate(2),
+        |n| n.connections.clear(),
+        |n| {
+            n.layers.pop();
+        },
+        |n| n.layers.clear(),
+        |n| n.layers.swap(2, 3),
+        |n| n.layers[2].layer_type = LayerType::Output,
+        |n| {
+            n.layers[1].neurons.pop();
+        },
+        |n| n.dimensions.total_neurons = 3,
+        |n| n.dimensions.input_size = 50,
+        |n| n.dimensions = NetworkDimensions::new(2, 40, 1),
+        |n| n.stats.error_count = 0,
+        |n| n.stats.pattern_count = usize::MAX,
+        |n| n.connections[9][0].from = usize::MAX,
+    ];
+
+    for (index, edit) in edits.iter().enumerate() {
+        let mut network = xor_network();
+        let _ = network.train_pattern(&TrainingPattern::new(vec![1.0, 0.0], vec![0.0], 0));
+        edit(&mut network);
+        let result = panic::catch_unwind(AssertUnwindSafe(|| exercise(&mut network)));
+        assert!(result.is_ok(), "edit {} panicked", index);
+    }
+
+    let mut ensemble = EDEnsemble::new(vec![xor_network()]).unwrap();
+    ensemble.members.clear();
+    assert!(ensemble.predict(&[0.0, 0.0]).is_err());
+    assert!(ensemble.predict_weighted(&[0.0, 0.0]).is_err());
+    let _ = ensemble.fit_aggregation_weights(&TrainingPattern::create_xor_dataset(), Default::default());
+    let _ = ensemble.to_string();
+}
+
+#[test]
+fn test_corrupted_files_and_external_data() {
+    let mut rng = StdRng::seed_from_u64(99);
+    let network = xor_network();
+    let text = json::to_string(&network).unwrap();
+
+    for cut in (0..text.len()).step_by(97) {
+        assert!(json::from_str::<EDNetwork>(&text[..cut]).is_err());
+    }
+    for _ in 0..200 {
+        let len = rng.random_range(0..64);
+        let garbage: String = (0..len).map(|_| char::from(rng.random_range(b' '..=b'~'))).collect();
+        let _ = json::from_str::<EDNetwork>(&garbage);
+        let _ = json::from_str::<Vec<f64>>(&garbage);
+    }
+    for fragment in [
+        "\"\\",
+        "\"\\u",
+        "\"\\uD800",
+        "\"\\uD800\\u",
+        "[1e999999]",
+        "-",
+        "[",
+        "{\"a\":",
+        "nul",
+        "1e",
+    ] {
+        let _ = json::from_str::<Vec<f64>>(fragment);
+        let _ = json::from_str::<String>(fragment);
+    }
+
+    #[cfg(feature = "binary")]
+    {
+        let path = std::env::temp_dir().join(format!("edla_no_panic_{}.bin", std::process::id()));
+        network.save_bin(&path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        for cut in (0..bytes.len()).step_by(61) {
+            std::fs::write(&path, &bytes[..cut]).unwrap();
+            assert!(EDNetwork::load_bin(&path).is_err());
+        }
+        for _ in 0..50 {
+            let mut flipped = bytes.clone();
+            for _ in 0..4 {
+                let index = rng.random_range(6..flipped.len());
+                flipped[index] = rng.random();
+            }
+            std::fs::write(&path, &flipped).unwrap();
+            if let Ok(mut loaded) = EDNetwork::load_bin(&path) {
+                exercise(&mut loaded);
+            }
+        }
+        std::fs::remove_file(&path).unwrap();
+    }
+
+    // Zero images whose remaining dimensions overflow a usize product
+    let mut images = vec![0, 0, 0x08, 4, 0, 0, 0, 0];
+    images.extend([0xff; 12]);
+    let labels = [0, 0, 0x08, 1, 0, 0, 0, 0];
+    assert!(matches!(
+        parse_idx_images(&images, &labels, None),
+        Err(EdError::SerializationError(_))
+    ));
+
+    let dims = NetworkDimensions {
+        total_neurons: 1,
+        ..NetworkDimensions::new(2, 2, 1)
+    };
+    let broken = IndexingConvention::edla_rs(dims);
+    let _ = broken.offsets();
+    assert!(remap(&[0.0], &broken, &broken).is_err());
+    let huge = IndexingConvention::kaneko_c(NetworkDimensions::new(usize::MAX, 1, 1));
+    let _ = huge.offsets();
+    assert!(remap(&[], &huge, &huge).is_err());
+
+    let empty = MetricSeries::new("empty", vec![], vec![]).unwrap();
+    let _ = empty.align(&empty, Alignment::Interpolate);
+    let _ = empty.ratio(&empty, Alignment::Truncate);
+    assert_eq!(empty.value_at(0), None);
+    assert!(MetricSeries::new("short", vec![1], vec![]).is_err());
+
+    let mut recorder = OrderRecorder::new(0);
+    let _ = recorder.record(1, &[]);
+    let _ = recorder.record(2, &[0]);
+    let _ = order_correlation(&recorder, &PatternCurveRecorder::new(3));
+}
+
+#[test]
+fn test_intentional_panics_are_listed() {
+    let triggers: Vec<(&str, fn())> = vec![
+        ("analysis::OrderRecorder::order", || drop(OrderRecorder::new(2).order(0))),
+        ("analysis::PatternCurveRecorder::curve", || {
+            let mut curves = PatternCurveRecorder::new(2);
+            curves.record(1, &[0.0, 0.0]).unwrap();
+            drop(curves.curve(2));
+        }),
+        ("analysis::PatternCurveRecorder::solved_epoch", || {
+            let mut curves = PatternCurveRecorder::new(2);
+            curves.record(1, &[0.0, 0.0]).unwrap();
+            let _ = curves.solved_epoch(5);
+        }),
+        ("training::TrainingPattern::create_parity_dataset", || {
+            drop(TrainingPattern::create_parity_dataset(usize::BITS as usize))
+        }),
+    ];
+
+    let listed: Vec<&str> = INTENTIONAL_PANICS.iter().map(|(name, _)| *name).collect();
+    let triggered: Vec<&str> = triggers.iter().map(|(name, _)| *name).collect();
+    assert_eq!(listed, triggered);
+
+    for (name, trigger) in triggers {
+        assert!(panic::catch_unwind(trigger).is_err(), "{} did not panic", name);
+    }
+}
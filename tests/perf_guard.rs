@@ -0,0 +1,114 @@
+//! Coarse performance guards, ignored by default. Run in release mode with:
+//! `cargo test --release --features testing --test perf_guard -- --ignored`
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use edla_rs::core::{
+    json,
+    network::{EDNetwork, NetworkConfig, NetworkDimensions, PredictBuffers},
+    training::TrainingPattern,
+};
+use edla_rs::testing::{CountingAllocator, count_allocations, inject_clone_per_pattern};
+
+#[global_allocator]
+static ALLOC: CountingAllocator = CountingAllocator;
+
+/// Held while measuring allocations, so the injected regression cannot leak into a parallel guard
+static INJECTION: Mutex<()> = Mutex::new(());
+
+/// Guard bounds, kept together so they are revised deliberately
+mod bounds {
+    use std::time::Duration;
+
+    /// Wall time for one training epoch over 64 patterns on the 100-neuron network
+    pub const EPOCH_WALL_TIME: Duration = Duration::from_millis(250);
+    /// Allocations per `predict_into` call once its buffers are warmed up
+    pub const PREDICT_ALLOCATIONS: usize = 0;
+    /// Serialized JSON size of the 100-neuron network, about 1.0 MB today
+    pub const JSON_BYTES: usize = 1_500_000;
+    /// Serialized binary size of the 100-neuron network, about 350 kB today
+    #[cfg(feature = "binary")]
+    pub const BINARY_BYTES: usize = 500_000;
+}
+
+/// 16 logical inputs (32 neurons) + 2 bias + 65 hidden + 1 output = 100 neurons
+fn guard_network() -> EDNetwork {
+    let config = NetworkConfig {
+        timesteps: 2,
+        ..NetworkConfig::default()
+    };
+
+    EDNetwork::new(NetworkDimensions::new(16, 65, 1), config).unwrap()
+}
+
+fn guard_patterns() -> Vec<TrainingPattern> {
+    (0..64)
+        .map(|id| {
+            let inputs = (0..16).map(|bit| ((id * 7 + bit) % 3 == 0) as u8 as f64).collect();
+            TrainingPattern::new(inputs, vec![(id % 2) as f64], id)
+        })
+        .collect()
+}
+
+fn predict_allocations(network: &EDNetwork, inputs: &[f64], inject: bool) -> usize {
+    let _guard = INJECTION.lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+    inject_clone_per_pattern(inject);
+
+    let mut buffers = PredictBuffers::default();
+    network.predict_into(inputs, &mut buffers).unwrap(); // warm up
+    let allocations = count_allocations(|| {
+        network.predict_into(inputs, &mut buffers).unwrap();
+    })
+    .1;
+
+    inject_clone_per_pattern(false);
+    allocations
+}
+
+#[test]
+#[ignore]
+fn guard_epoch_wall_time() {
+    let mut network = guard_network();
+    network.training_data = guard_patterns();
+    assert_eq!(network.dimensions.total_neurons, 100);
+
+    let start = Instant::now();
+    network.train_epoch().unwrap();
+    let elapsed: Duration = start.elapsed();
+
+    assert!(elapsed < bounds::EPOCH_WALL_TIME, "epoch took {:?}", elapsed);
+}
+
+#[test]
+#[ignore]
+fn guard_predict_allocations() {
+    let network = guard_network();
+    let allocations = predict_allocations(&network, &guard_patterns()[0].inputs, false);
+
+    assert_eq!(allocations, bounds::PREDICT_ALLOCATIONS, "predict allocated in steady state");
+}
+
+#[test]
+#[ignore]
+fn guard_serialized_size() {
+    let network = guard_network();
+
+    let json_bytes = json::to_string(&network).unwrap().len();
+    assert!(json_bytes <= bounds::JSON_BYTES, "JSON took {} bytes", json_bytes);
+
+    #[cfg(feature = "binary")]
+    {
+        let binary_bytes = edla_rs::core::binary::to_bytes(&network).unwrap().len();
+        assert!(binary_bytes <= bounds::BINARY_BYTES, "binary took {} bytes", binary_bytes);
+    }
+}
+
+#[test]
+#[ignore]
+fn guard_detects_clone_per_pattern() {
+    let network = guard_network();
+    let allocations = predict_allocations(&network, &guard_patterns()[0].inputs, true);
+
+    assert!(allocations > bounds::PREDICT_ALLOCATIONS, "injected regression went unnoticed");
+}
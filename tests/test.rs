@@ -1,8 +1,29 @@
 use edla_rs::core::{
-    neuron::{ErrorChannels, NeuronType},
-    training::TrainingPattern,
-    utils::sigmoid,
+    MAX_NETWORK_SIZE, MAX_OUTPUT_NETWORKS,
+    analysis::{Alignment, MetricSeries, OrderRecorder, PatternCurveRecorder, StatsField, capacity_report, order_correlation_within},
+    diagnostics::{FindingCode, preflight},
+    ensemble::{AggregationScheme, EDEnsemble, EnsembleMode},
+    error::EdError,
+    export::{IndexingConvention, remap},
+    hook::{ConnectionUpdateHook, UpdateContext},
+    json,
+    network::{
+        ConfusionMatrix, EDNetwork, EDNetworkBuilder, LayerEiRatios, LayerLearningRates, LayerType, LearningSchedule, LearningStats,
+        NetworkBuilder, NetworkConfig, NetworkConfigBuilder, NetworkDimensions, PredictBuffers, RepairReport, UpdateMode,
+    },
+    neuron::{Connection, ErrorChannels, ErrorSplit, Neuron, NeuronOutputMode, NeuronType},
+    session::{SessionStatus, StopReason, StoppingCriteria, TrainingOptions, TrainingSession},
+    training::{
+        DATASET_FORMAT_VERSION, MAX_ADDER_BITS, MAX_ENCODER_SIZE, NormalizationMethod, Normalizer, TrainingPattern, load_dataset,
+        loaders::{LibsvmLabels, load_idx_images, load_libsvm, parse_idx_images, parse_libsvm},
+        one_hot, save_dataset, split,
+    },
+    utils::{
+        Activation, Loss, OutputActivation, WeightInit, argmax, argmax_label, random_gaussian, random_weight, random_weight_symmetric,
+        sigmoid, softmax, tanh_activation, tanh_derivative,
+    },
 };
+use rand::{RngExt, SeedableRng, rngs::StdRng, seq::SliceRandom};
 
 #[test]
 fn test_neuron_type_alternation() {
@@ -23,6 +44,107 @@ fn test_error_channel_creation() {
     assert_eq!(negative_error.inhibitory, 0.3);
 }
 
+#[test]
+fn test_soft_error_split_approaches_hard_split() {
+    for error in [0.5, -0.3, 1e-3, -2.0] {
+        let hard = ErrorChannels::from_prediction_error(error);
+        let soft = ErrorChannels::from_prediction_error_soft(error, 1e-9);
+        assert!((soft.excitatory - hard.excitatory).abs() < 1e-12, "{}", error);
+        assert!((soft.inhibitory - hard.inhibitory).abs() < 1e-12, "{}", error);
+
+        // A warm split feeds both channels but keeps the net signal's sign
+        let warm = ErrorSplit::Soft {
+            temperature: 0.5,
+        }
+        .split(error);
+        assert!(warm.excitatory > 0.0 && warm.inhibitory > 0.0);
+        assert_eq!((warm.excitatory - warm.inhibitory).signum(), error.signum());
+        assert!((warm.excitatory + warm.inhibitory - error.abs()).abs() < 1e-12);
+    }
+    assert!(!ErrorChannels::from_prediction_error_soft(0.0, 0.5).has_error_signal());
+
+    let trained = |error_split| {
+        let config = NetworkConfig {
+            error_split,
+            ..NetworkConfig::default()
+        };
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(4)
+            .outputs(1)
+            .config(config)
+            .seed(4)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        network.train(5).unwrap();
+        weights(&network)
+    };
+    let hard = trained(ErrorSplit::Hard);
+    let cold = trained(ErrorSplit::Soft {
+        temperature: 1e-9,
+    });
+    assert!(hard.iter().zip(&cold).all(|(a, b)| (a - b).abs() < 1e-9));
+    assert_ne!(
+        trained(ErrorSplit::Soft {
+            temperature: 0.5
+        }),
+        hard
+    );
+
+    let invalid = NetworkConfig {
+        error_split: ErrorSplit::Soft {
+            temperature: 0.0,
+        },
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(invalid.validate(), Err(EdError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_stochastic_firing_is_seeded_and_training_only() {
+    let mut rng = StdRng::seed_from_u64(1);
+    assert_eq!(NeuronOutputMode::Stochastic.fire(1.0, &mut rng), 1.0);
+    assert_eq!(NeuronOutputMode::Stochastic.fire(0.0, &mut rng), 0.0);
+    assert_eq!(NeuronOutputMode::Deterministic.fire(0.3, &mut rng), 0.3);
+    let mut neuron = Neuron::new(NeuronType::Excitatory, 0);
+    neuron.input = 0.2;
+    neuron.activate_with(Activation::Sigmoid, 0.4, NeuronOutputMode::Stochastic, &mut rng);
+    assert!(neuron.output == 0.0 || neuron.output == 1.0);
+
+    let trained = |neuron_output_mode| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(NetworkConfig {
+                neuron_output_mode,
+                ..NetworkConfig::default()
+            })
+            .seed(21)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        network.train(30).unwrap();
+        network
+    };
+
+    let mut stochastic = trained(NeuronOutputMode::Stochastic);
+    let replay = trained(NeuronOutputMode::Stochastic);
+    assert_eq!(weights(&stochastic), weights(&replay));
+    assert_eq!(stochastic.stats.error_history, replay.stats.error_history);
+    assert_ne!(weights(&stochastic), weights(&trained(NeuronOutputMode::Deterministic)));
+    // Stats come from the continuous activations, so no epoch error is a whole number of misses
+    assert!(stochastic.stats.error_history.iter().all(|error| error.fract() != 0.0));
+
+    // Inference stays deterministic unless sampling is asked for
+    let first = stochastic.forward(&[1.0, 0.0]).unwrap();
+    assert_eq!(stochastic.forward(&[1.0, 0.0]).unwrap(), first);
+    assert_eq!(stochastic.predict(&[1.0, 0.0]).unwrap(), first);
+    let sampled: Vec<Vec<f64>> = (0..20).map(|_| stochastic.forward_sampled(&[1.0, 0.0]).unwrap()).collect();
+    assert!(sampled.iter().any(|outputs| outputs != &first));
+}
+
 #[test]
 fn test_xor_dataset_creation() {
     let xor_data = TrainingPattern::create_xor_dataset();
@@ -34,6 +156,309 @@ fn test_xor_dataset_creation() {
     assert_eq!(xor_data[3].targets[0], 0.0); // [1,1] -> 0
 }
 
+#[test]
+fn test_gate_dataset_creation() {
+    let targets = |dataset: Vec<TrainingPattern>| -> Vec<(Vec<f64>, f64, usize)> {
+        dataset.into_iter().map(|p| (p.inputs, p.targets[0], p.id)).collect()
+    };
+    let inputs = [vec![0.0, 0.0], vec![1.0, 0.0], vec![0.0, 1.0], vec![1.0, 1.0]];
+    let expect = |outputs: [f64; 4]| -> Vec<(Vec<f64>, f64, usize)> {
+        inputs
+            .iter()
+            .cloned()
+            .zip(outputs)
+            .enumerate()
+            .map(|(id, (i, t))| (i, t, id))
+            .collect()
+    };
+
+    assert_eq!(targets(TrainingPattern::create_and_dataset()), expect([0.0, 0.0, 0.0, 1.0]));
+    assert_eq!(targets(TrainingPattern::create_or_dataset()), expect([0.0, 1.0, 1.0, 1.0]));
+    assert_eq!(targets(TrainingPattern::create_nand_dataset()), expect([1.0, 1.0, 1.0, 0.0]));
+}
+
+#[test]
+fn test_csv_dataset_loading() {
+    let path = std::env::temp_dir().join(format!("edla_patterns_{}.csv", std::process::id()));
+    let load = |text: &str, n_inputs| {
+        std::fs::write(&path, text).unwrap();
+        TrainingPattern::from_csv(&path, n_inputs)
+    };
+
+    let with_header = load("a,b,xor,and\n0,0,0,0\n1,0,1,0\n\n0,1,1,0\n1, 1, 0, 1\n", 2).unwrap();
+    assert_eq!(with_header.len(), 4);
+    assert_eq!(with_header[3].inputs, vec![1.0, 1.0]);
+    assert_eq!(with_header[3].targets, vec![0.0, 1.0]);
+    assert_eq!(with_header.iter().map(|p| p.id).collect::<Vec<_>>(), vec![0, 1, 2, 3]);
+
+    let headerless = load("0.5,1\n0.25,0\n", 1).unwrap();
+    assert_eq!(headerless.len(), 2);
+    assert_eq!(headerless[0].inputs, vec![0.5]);
+
+    assert!(matches!(load("0,0,1\n1,0\n", 2), Err(EdError::DimensionMismatch { .. })));
+    assert!(matches!(load("0,0\n", 2), Err(EdError::DimensionMismatch { .. })));
+    assert!(matches!(load("x,y\n0,1\n1,oops\n", 1), Err(EdError::SerializationError(_))));
+    assert_eq!(load("x,y\n", 1).err(), Some(EdError::EmptyTrainingSet));
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(TrainingPattern::from_csv(&path, 1), Err(EdError::Io(_))));
+}
+
+/// Three 2x2 images labeled 3, 0 and 9
+fn idx_fixture() -> (Vec<u8>, Vec<u8>) {
+    let mut images = vec![0, 0, 0x08, 3, 0, 0, 0, 3, 0, 0, 0, 2, 0, 0, 0, 2];
+    images.extend([0, 255, 255, 0, 0, 0, 0, 0, 51, 102, 153, 204]);
+    let labels = vec![0, 0, 0x08, 1, 0, 0, 0, 3, 3, 0, 9];
+    (images, labels)
+}
+
+#[test]
+fn test_idx_loader() {
+    let dir = std::env::temp_dir();
+    let images_path = dir.join(format!("edla_idx_images_{}", std::process::id()));
+    let labels_path = dir.join(format!("edla_idx_labels_{}", std::process::id()));
+    let (images, labels) = idx_fixture();
+    std::fs::write(&images_path, &images).unwrap();
+    std::fs::write(&labels_path, &labels).unwrap();
+
+    let patterns = load_idx_images(&images_path, &labels_path, None).unwrap();
+    assert_eq!(patterns.len(), 3);
+    assert_eq!(patterns[0].inputs, vec![0.0, 1.0, 1.0, 0.0]);
+    assert_eq!(patterns[2].inputs, vec![0.2, 0.4, 0.6, 0.8]);
+    assert_eq!(
+        patterns.iter().map(|p| argmax(&p.targets)).collect::<Vec<_>>(),
+        vec![Some(3), Some(0), Some(9)]
+    );
+    assert!(
+        patterns
+            .iter()
+            .all(|p| p.targets.len() == 10 && p.targets.iter().sum::<f64>() == 1.0)
+    );
+    assert_eq!(load_idx_images(&images_path, &labels_path, Some(2)).unwrap().len(), 2);
+
+    let mut bad_label = labels.clone();
+    bad_label[9] = 10;
+    assert!(matches!(
+        parse_idx_images(&images, &bad_label, None),
+        Err(EdError::SerializationError(_))
+    ));
+    assert!(matches!(
+        parse_idx_images(&images[..images.len() - 1], &labels, None),
+        Err(EdError::SerializationError(_))
+    ));
+    assert!(matches!(
+        parse_idx_images(&images, &labels[..10], None),
+        Err(EdError::SerializationError(_))
+    ));
+    assert!(matches!(
+        parse_idx_images(&labels, &labels, None),
+        Err(EdError::SerializationError(_))
+    ));
+
+    // The same images, gzip-compressed as one fixed-Huffman, dynamic-Huffman or stored block
+    let fixed: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x63, 0x60, 0xe0, 0x60, 0x66, 0x60, 0x60, 0x00, 0x61, 0x26, 0x30, 0xfe,
+        0xff, 0x9f, 0x01, 0x04, 0x8c, 0xd3, 0x66, 0x9e, 0x01, 0x00, 0xc2, 0x50, 0x23, 0x48, 0x1c, 0x00, 0x00, 0x00,
+    ];
+    let dynamic: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x05, 0xc1, 0x41, 0x11, 0x00, 0x30, 0x08, 0x00, 0x20, 0x4e, 0x3f, 0xeb,
+        0x63, 0x27, 0x3b, 0xd9, 0x69, 0x9d, 0x14, 0x78, 0x89, 0x44, 0x20, 0xec, 0x82, 0xea, 0xf9, 0x07, 0xc2, 0x50, 0x23, 0x48, 0x1c, 0x00,
+        0x00, 0x00,
+    ];
+    let stored: &[u8] = &[
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x04, 0x03, 0x01, 0x1c, 0x00, 0xe3, 0xff, 0x00, 0x00, 0x08, 0x03, 0x00, 0x00, 0x00,
+        0x03, 0x00, 0x00, 0x00, 0x02, 0x00, 0x00, 0x00, 0x02, 0x00, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00, 0x00, 0x33, 0x66, 0x99, 0xcc, 0xc2,
+        0x50, 0x23, 0x48, 0x1c, 0x00, 0x00, 0x00,
+    ];
+    for gzipped in [fixed, dynamic, stored] {
+        std::fs::write(&images_path, gzipped).unwrap();
+        let loaded = load_idx_images(&images_path, &labels_path, None);
+        #[cfg(feature = "gzip")]
+        assert_eq!(
+            loaded.unwrap().iter().map(|p| &p.inputs).collect::<Vec<_>>(),
+            patterns.iter().map(|p| &p.inputs).collect::<Vec<_>>()
+        );
+        #[cfg(not(feature = "gzip"))]
+        assert!(matches!(loaded, Err(EdError::SerializationError(_))));
+    }
+
+    std::fs::remove_file(&images_path).unwrap();
+    std::fs::remove_file(&labels_path).unwrap();
+}
+
+#[cfg(feature = "gzip")]
+#[test]
+fn test_gzip_back_references_stay_within_their_member() {
+    let dir = std::env::temp_dir();
+    let images_path = dir.join(format!("edla_gzip_members_{}", std::process::id()));
+    let labels_path = dir.join(format!("edla_gzip_members_labels_{}", std::process::id()));
+    // A 2x2 image of 7s split over two members: the header and one pixel, then a 3-byte copy of
+    // that pixel, which the second member has no earlier output of its own to copy from
+    let gzipped = [
+        0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x02, 0x03, 0x63, 0x60, 0xe0, 0x60, 0x66, 0x60, 0x60, 0x60, 0x04, 0x62, 0x26, 0x10,
+        0x66, 0x07, 0x00, 0xf5, 0x25, 0x5f, 0x74, 0x11, 0x00, 0x00, 0x00, 0x1f, 0x8b, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x03, 0x03,
+        0x02, 0x00, 0xf3, 0xcc, 0x2b, 0x2b, 0x03, 0x00, 0x00, 0x00,
+    ];
+    std::fs::write(&images_path, gzipped).unwrap();
+    std::fs::write(&labels_path, [0, 0, 0x08, 1, 0, 0, 0, 1, 5]).unwrap();
+
+    let loaded = load_idx_images(&images_path, &labels_path, None);
+    std::fs::remove_file(&images_path).unwrap();
+    std::fs::remove_file(&labels_path).unwrap();
+    match loaded {
+        Err(EdError::SerializationError(msg)) => assert!(msg.contains("before start of member"), "{}", msg),
+        other => panic!("{:?}", other.map(|p| p.len())),
+    }
+}
+
+#[test]
+fn test_dataset_json_round_trip() {
+    let path = std::env::temp_dir().join(format!("edla_dataset_{}.json", std::process::id()));
+    let patterns = TrainingPattern::create_adder_dataset(2).unwrap();
+    save_dataset(&path, &patterns).unwrap();
+
+    let loaded = load_dataset(&path).unwrap();
+    assert_eq!(loaded.len(), patterns.len());
+    for (loaded, pattern) in loaded.iter().zip(&patterns) {
+        assert_eq!(
+            (loaded.id, &loaded.inputs, &loaded.targets),
+            (pattern.id, &pattern.inputs, &pattern.targets)
+        );
+    }
+
+    // Truncated, reshaped and future-version files are all rejected
+    let text = std::fs::read_to_string(&path).unwrap();
+    std::fs::write(&path, &text[..text.len() / 2]).unwrap();
+    assert!(matches!(load_dataset(&path), Err(EdError::SerializationError(_))));
+
+    let mut reshaped = patterns.clone();
+    reshaped[5].targets.pop();
+    assert!(matches!(save_dataset(&path, &reshaped), Err(EdError::SerializationError(_))));
+    std::fs::write(&path, text.replace("\"target_size\": 3", "\"target_size\": 2")).unwrap();
+    match load_dataset(&path) {
+        Err(EdError::SerializationError(msg)) => assert!(msg.starts_with("pattern 0 "), "{}", msg),
+        other => panic!("{:?}", other.map(|p| p.len())),
+    }
+
+    let version = format!("\"version\": {}", DATASET_FORMAT_VERSION);
+    std::fs::write(&path, text.replace(&version, "\"version\": 99")).unwrap();
+    assert!(matches!(
+        load_dataset(&path),
+        Err(EdError::VersionMismatch {
+            found: 99,
+            ..
+        })
+    ));
+
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(save_dataset(&path, &[]), Err(EdError::EmptyTrainingSet)));
+}
+
+#[test]
+fn test_libsvm_loader() {
+    // Multi-class file with a comment, a blank line and missing indices
+    let path = std::env::temp_dir().join(format!("edla_libsvm_{}", std::process::id()));
+    std::fs::write(&path, "# iris-like\n1 1:0.5 3:1.0\n3 2:0.25 # trailing\n\n2 qid:4 1:1 4:-1\n").unwrap();
+    let patterns = load_libsvm(&path, None, &LibsvmLabels::Classes(vec![1, 2, 3])).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(patterns.len(), 3);
+    assert_eq!(patterns[0].inputs, vec![0.5, 0.0, 1.0, 0.0]);
+    assert_eq!(patterns[1].inputs, vec![0.0, 0.25, 0.0, 0.0]);
+    assert_eq!(patterns[2].inputs, vec![1.0, 0.0, 0.0, -1.0]);
+    assert_eq!(patterns[1].targets, vec![0.0, 0.0, 1.0]);
+    assert_eq!(patterns[2].targets, vec![0.0, 1.0, 0.0]);
+    assert_eq!(patterns[2].id, 2);
+
+    // Binary ±1 labels and an explicit feature count
+    let binary = parse_libsvm("+1 2:1\n-1 1:1\n", Some(5), &LibsvmLabels::Binary).unwrap();
+    assert_eq!(binary[0].inputs, vec![0.0, 1.0, 0.0, 0.0, 0.0]);
+    assert_eq!(binary.iter().map(|p| p.targets[0]).collect::<Vec<_>>(), vec![1.0, 0.0]);
+
+    for bad in ["1 2:1 1:1", "1 1:1 1:2", "1 0:1", "1 1:x", "2 1:1", "0.5 1:1", "1 6:1"] {
+        assert!(
+            matches!(
+                parse_libsvm(bad, Some(5), &LibsvmLabels::Binary),
+                Err(EdError::SerializationError(_))
+            ),
+            "{}",
+            bad
+        );
+    }
+    assert!(matches!(
+        parse_libsvm("# nothing\n", None, &LibsvmLabels::Binary),
+        Err(EdError::EmptyTrainingSet)
+    ));
+}
+
+#[test]
+fn test_shuffle_slice_preserves_ids() {
+    let mut patterns = TrainingPattern::create_parity_dataset(4);
+    let mut rng = StdRng::seed_from_u64(3);
+    TrainingPattern::shuffle_slice(&mut patterns, &mut rng);
+
+    let ids: Vec<usize> = patterns.iter().map(|p| p.id).collect();
+    assert_ne!(ids, (0..16).collect::<Vec<_>>());
+    let reference = TrainingPattern::create_parity_dataset(4);
+    for pattern in &patterns {
+        assert_eq!(pattern.inputs, reference[pattern.id].inputs);
+        assert_eq!(pattern.targets, reference[pattern.id].targets);
+    }
+}
+
+#[test]
+fn test_encoder_and_adder_dataset_creation() {
+    let encoder = TrainingPattern::create_encoder_dataset(8).unwrap();
+    assert_eq!(encoder.len(), 8);
+    for (k, pattern) in encoder.iter().enumerate() {
+        assert_eq!(pattern.id, k);
+        assert_eq!(pattern.inputs, pattern.targets);
+        assert_eq!(pattern.inputs.iter().sum::<f64>(), 1.0);
+        assert_eq!(pattern.inputs[k], 1.0);
+    }
+    for n in [0, 1, 6, 12, MAX_ENCODER_SIZE * 2] {
+        assert!(matches!(TrainingPattern::create_encoder_dataset(n), Err(EdError::InvalidConfig(_))));
+    }
+
+    let adder = TrainingPattern::create_adder_dataset(2).unwrap();
+    assert_eq!(adder.len(), 16);
+    let value = |bits: &[f64]| bits.iter().enumerate().map(|(i, &bit)| (bit as usize) << i).sum::<usize>();
+    for (id, pattern) in adder.iter().enumerate() {
+        assert_eq!(pattern.id, id);
+        assert_eq!((pattern.inputs.len(), pattern.targets.len()), (4, 3));
+        assert_eq!(value(&pattern.targets), value(&pattern.inputs[..2]) + value(&pattern.inputs[2..]));
+    }
+    // 3 + 3 = 6 is the last pattern
+    assert_eq!(adder[15].inputs, vec![1.0, 1.0, 1.0, 1.0]);
+    assert_eq!(adder[15].targets, vec![0.0, 1.0, 1.0]);
+    assert!(TrainingPattern::create_adder_dataset(0).is_err());
+    assert!(TrainingPattern::create_adder_dataset(MAX_ADDER_BITS + 1).is_err());
+}
+
+#[test]
+fn test_one_hot_round_trips_through_argmax_label() {
+    let binary = one_hot(&[1, 0, 1], 2).unwrap();
+    assert_eq!(binary, vec![vec![0.0, 1.0], vec![1.0, 0.0], vec![0.0, 1.0]]);
+
+    let labels = [4, 0, 2, 3, 1, 2];
+    let targets = one_hot(&labels, 5).unwrap();
+    assert!(targets.iter().all(|t| t.len() == 5 && t.iter().sum::<f64>() == 1.0));
+    let decoded: Vec<usize> = targets.iter().map(|t| argmax_label(t).unwrap()).collect();
+    assert_eq!(decoded, labels);
+
+    let pattern = TrainingPattern::classification(vec![0.5, 0.5], 3, 5, 7).unwrap();
+    assert_eq!((pattern.id, pattern.targets.clone()), (7, targets[3].clone()));
+    assert_eq!(argmax_label(&[0.1, 0.7, 0.2]).unwrap(), 1);
+
+    assert!(matches!(one_hot(&[0, 5], 5), Err(EdError::InvalidConfig(_))));
+    assert!(matches!(one_hot(&[0], MAX_NETWORK_SIZE + 1), Err(EdError::InvalidConfig(_))));
+    assert!(matches!(
+        TrainingPattern::classification(vec![0.0], 2, 2, 0),
+        Err(EdError::InvalidConfig(_))
+    ));
+    assert!(argmax_label(&[]).is_err());
+    assert!(matches!(argmax_label(&[0.1, f64::NAN]), Err(EdError::NumericalInstability(_))));
+}
+
 #[test]
 fn test_sigmoid_function() {
     let result = sigmoid(0.0, 0.4);
@@ -44,4 +469,3126 @@ fn test_sigmoid_function() {
 
     let negative = sigmoid(-1.0, 0.4);
     assert!(negative < 0.5);
+
+    // Extreme inputs and zero steepness saturate without leaving (0, 1)
+    for value in [
+        sigmoid(f64::MAX, 0.4),
+        sigmoid(-1e308, 0.4),
+        sigmoid(1.0, 0.0),
+        sigmoid(-1.0, 0.0),
+        sigmoid(0.0, 0.0),
+    ] {
+        assert!(value.is_finite() && value > 0.0 && value < 1.0, "{}", value);
+    }
+    assert_eq!(sigmoid(0.0, 0.0), 0.5);
+
+    // Sweeping the steepness from zero to huge never lets NaN out and keeps the curve monotone in x
+    for steepness in [0.0, f64::MIN_POSITIVE, 1e-300, 1e-12, 0.4, 1e12, 1e300, f64::INFINITY] {
+        let values: Vec<f64> = [-1e6, -1.0, -1e-9, 0.0, 1e-9, 1.0, 1e6]
+            .iter()
+            .map(|&x| sigmoid(x, steepness))
+            .collect();
+        assert!(
+            values.iter().all(|v| v.is_finite() && (0.0..=1.0).contains(v)),
+            "{}: {:?}",
+            steepness,
+            values
+        );
+        assert!(values.windows(2).all(|w| w[0] <= w[1]), "{}: {:?}", steepness, values);
+        assert!(tanh_activation(0.0, steepness).is_finite(), "{}", steepness);
+    }
+    assert!(sigmoid(1.0, 1e-300) > 0.999 && sigmoid(-1.0, 1e-300) < 0.001);
+    assert!((sigmoid(1.0, 1e300) - 0.5).abs() < 1e-12);
+}
+
+#[test]
+fn test_tanh_function() {
+    assert_eq!(tanh_activation(0.0, 0.4), 0.0);
+    assert!(tanh_activation(10.0, 0.4) > 0.99);
+    assert!(tanh_activation(-10.0, 0.4) < -0.99);
+
+    let values: Vec<f64> = (-20..=20).map(|i| tanh_activation(i as f64 * 0.05, 0.4)).collect();
+    assert!(values.windows(2).all(|w| w[0] < w[1]));
+
+    // Same input scaling as sigmoid: tanh(x) = 2 * sigmoid(2x) - 1
+    assert!((tanh_activation(0.3, 0.4) - (2.0 * sigmoid(0.6, 0.4) - 1.0)).abs() < 1e-12);
+    assert_eq!(tanh_derivative(0.0), 1.0);
+}
+
+#[test]
+fn test_dry_run_valid_setup() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+    let before: Vec<f64> = network.connections.iter().flatten().map(|c| c.weight).collect();
+
+    assert!(network.dry_run(&TrainingPattern::create_xor_dataset()).is_ok());
+
+    let after: Vec<f64> = network.connections.iter().flatten().map(|c| c.weight).collect();
+    assert_eq!(before, after); // weights are not committed
+}
+
+#[test]
+fn test_dry_run_leaves_no_pending_batch_deltas() {
+    let config = NetworkConfig {
+        update_mode: UpdateMode::Batch,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), config, 6).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    let mut untouched = network.clone();
+
+    network.dry_run(&network.training_data.clone()).unwrap();
+    network.train_epoch().unwrap();
+    untouched.train_epoch().unwrap();
+    assert_eq!(weights(&network), weights(&untouched));
+}
+
+#[test]
+fn test_dry_run_rejects_disconnected_output() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+    let output = network.dimensions.total_neurons - 1;
+    for row in &mut network.connections {
+        row[output].connection_enabled = false;
+    }
+
+    match network.dry_run(&TrainingPattern::create_xor_dataset()) {
+        Err(EdError::InvalidTopology(msg)) => assert!(msg.contains("output neuron")),
+        other => panic!("expected topology error, got {:?}", other),
+    }
+}
+
+#[test]
+fn test_dry_run_rejects_mismatched_patterns() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(3, 4, 1), NetworkConfig::default()).unwrap();
+    let result = network.dry_run(&TrainingPattern::create_xor_dataset());
+
+    assert_eq!(
+        result,
+        Err(EdError::DimensionMismatch {
+            expected: 3,
+            got: 2
+        })
+    );
+}
+
+#[test]
+fn test_network_construction_layout() {
+    let network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+    let sizes: Vec<(LayerType, usize)> = network.layers.iter().map(|l| (l.layer_type, l.neurons.len())).collect();
+
+    assert_eq!(
+        sizes,
+        vec![
+            (LayerType::Bias, 2),
+            (LayerType::Input, 4),
+            (LayerType::Hidden, 4),
+            (LayerType::Output, 1)
+        ]
+    );
+    assert_eq!(network.connections.len(), network.dimensions.total_neurons);
+    assert!(network.connections.iter().all(|row| row.len() == network.dimensions.total_neurons));
+
+    for (from, row) in network.connections.iter().enumerate() {
+        for (to, connection) in row.iter().enumerate() {
+            assert_eq!((connection.from, connection.to), (from, to));
+            if from == to || to < 6 {
+                assert!(!connection.connection_enabled); // no self loops, nothing feeds bias/input
+            }
+        }
+    }
+
+    // Bias neurons feed every hidden and output neuron
+    for to in 6..network.dimensions.total_neurons {
+        assert!(network.connections[0][to].connection_enabled);
+        assert!(network.connections[1][to].connection_enabled);
+    }
+}
+
+#[test]
+fn test_network_construction_inhibitory_inputs_flag() {
+    let config = NetworkConfig {
+        flag_inhibitory_inputs: false,
+        ..NetworkConfig::default()
+    };
+    let network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), config).unwrap();
+    let inputs = &network.layers[1];
+
+    for (local, neuron) in inputs.neurons.iter().enumerate() {
+        let enabled = network.connections[2 + local].iter().filter(|c| c.connection_enabled).count();
+        assert_eq!(enabled > 0, neuron.is_excitatory());
+    }
+}
+
+#[test]
+fn test_inhibitory_inputs_flag_changes_input_fan_out() {
+    let enabled_from_inputs = |flag_inhibitory_inputs| {
+        let config = NetworkConfig {
+            flag_inhibitory_inputs,
+            ..NetworkConfig::default()
+        };
+        let network = EDNetwork::new(NetworkDimensions::new(3, 4, 1), config).unwrap();
+        network
+            .layer_range(LayerType::Input)
+            .flat_map(|from| network.connections[from].iter())
+            .filter(|c| c.connection_enabled)
+            .count()
+    };
+
+    // Every input neuron feeds the 4 hidden neurons; multilayer mode cuts the output shortcut
+    assert_eq!(enabled_from_inputs(true), 6 * 4);
+    assert_eq!(enabled_from_inputs(false), 3 * 4);
+}
+
+#[test]
+fn test_multilayer_flag_blocks_input_to_output_shortcuts() {
+    let shortcuts = |flag_multilayer| {
+        let config = NetworkConfig {
+            flag_multilayer,
+            ..NetworkConfig::default()
+        };
+        let network = EDNetwork::new(NetworkDimensions::new(3, 4, 2), config).unwrap();
+        network
+            .layer_range(LayerType::Input)
+            .flat_map(|from| network.layer_range(LayerType::Output).map(move |to| (from, to)))
+            .map(|(from, to)| network.connection(from, to).unwrap().connection_enabled)
+            .collect::<Vec<bool>>()
+    };
+
+    assert_eq!(shortcuts(true), vec![false; 6 * 2]);
+    assert_eq!(shortcuts(false), vec![true; 6 * 2]);
+}
+
+#[cfg(feature = "binary")]
+#[test]
+fn test_network_without_inhibitory_inputs_builds_single_copies() {
+    let config = NetworkConfig {
+        flag_inhibitory_inputs: false,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(3, 4, 1), config, 5).unwrap();
+
+    assert!(!network.dimensions.inhibitory_inputs);
+    assert_eq!(network.dimensions.total_neurons, NetworkDimensions::new(3, 4, 1).total_neurons - 3);
+    assert_eq!(network.connections.len(), network.dimensions.total_neurons);
+    assert_eq!(network.layers[1].neurons.len(), 3);
+    assert!(network.layers[1].neurons.iter().all(|neuron| neuron.is_excitatory()));
+
+    let path = std::env::temp_dir().join(format!("edla_single_inputs_{}.bin", std::process::id()));
+    network.save_bin(&path).unwrap();
+    let mut loaded = EDNetwork::load_bin(&path).unwrap();
+    std::fs::remove_file(&path).ok();
+
+    assert_eq!(loaded.dimensions, network.dimensions);
+    assert_eq!(
+        loaded.forward(&[1.0, 0.0, 1.0]).unwrap(),
+        network.forward(&[1.0, 0.0, 1.0]).unwrap()
+    );
+}
+
+#[test]
+fn test_network_construction_rejects_oversized() {
+    let result = EDNetwork::new(NetworkDimensions::new(400, 200, 10), NetworkConfig::default());
+
+    assert!(matches!(
+        result,
+        Err(EdError::NetworkTooLarge {
+            neurons: 1012,
+            max: 1000
+        })
+    ));
+}
+
+#[test]
+fn test_network_construction_rejects_empty_layers() {
+    for dimensions in [
+        NetworkDimensions::new(0, 4, 1),
+        NetworkDimensions::new(2, 0, 1),
+        NetworkDimensions::new(2, 4, 0),
+    ] {
+        let result = EDNetwork::new_seeded(dimensions.clone(), NetworkConfig::default(), 1);
+        assert!(matches!(result, Err(EdError::InvalidConfig(_))), "{:?}", dimensions);
+    }
+}
+
+#[test]
+fn test_forward_pass() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+    let outputs = network.forward(&[1.0, 0.0]).unwrap();
+
+    assert_eq!(outputs.len(), 1);
+    assert!(outputs[0] > 0.0 && outputs[0] < 1.0);
+
+    // Both halves of each doubled input pair carry the logical input value
+    let inputs: Vec<f64> = network.layers[1].neurons.iter().map(|n| n.output).collect();
+    assert_eq!(inputs, vec![1.0, 1.0, 0.0, 0.0]);
+
+    let result = network.forward(&[1.0]);
+    assert_eq!(
+        result,
+        Err(EdError::DimensionMismatch {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn test_loop_cutting_removes_feedback_edges() {
+    let feedback_edges = |flag_loop_cutting| {
+        let config = NetworkConfig {
+            flag_loop_cutting,
+            ..NetworkConfig::default()
+        };
+        let network = EDNetwork::new(NetworkDimensions::new(2, 4, 2), config).unwrap();
+        let layer_of = |index: usize| {
+            network
+                .layers
+                .iter()
+                .position(|layer| network.layer_range(layer.layer_type).contains(&index))
+        };
+        network
+            .connections
+            .iter()
+            .flatten()
+            .filter(|c| c.connection_enabled && layer_of(c.from) > layer_of(c.to))
+            .count()
+    };
+
+    assert_eq!(feedback_edges(true), 0);
+    // Without cutting, outputs feed back into the hidden layer
+    assert!(feedback_edges(false) > 0);
+}
+
+#[test]
+fn test_forward_recurrent_timesteps() {
+    let config = NetworkConfig {
+        flag_loop_cutting: false,
+        timesteps: 1,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), config).unwrap();
+    let single_step = network.forward(&[1.0, 1.0]).unwrap();
+
+    network.config.timesteps = 4;
+    let recurrent = network.forward(&[1.0, 1.0]).unwrap();
+    assert_ne!(single_step, recurrent);
+
+    // A forward pass starts from a clean state, so repeating it is deterministic
+    assert_eq!(recurrent, network.forward(&[1.0, 1.0]).unwrap());
+
+    // Disabled connections contribute nothing: an output with no inputs sits at sigmoid(0)
+    let output = network.dimensions.total_neurons - 1;
+    for row in &mut network.connections {
+        row[output].connection_enabled = false;
+    }
+    assert_eq!(network.forward(&[1.0, 1.0]).unwrap(), vec![0.5]);
+
+    assert!(matches!(network.forward(&[f64::NAN, 1.0]), Err(EdError::NumericalInstability(_))));
+}
+
+#[test]
+fn test_remap_round_trip_is_identity() {
+    let network = EDNetwork::new(NetworkDimensions::new(3, 5, 2), NetworkConfig::default()).unwrap();
+    let native = network.indexing_convention();
+    let kaneko = IndexingConvention::kaneko_c(network.dimensions.clone());
+
+    let weights: Vec<f64> = network.connections.iter().flatten().map(|c| c.weight).collect();
+    let exported = network.export_weights(&kaneko).unwrap();
+    assert_ne!(exported, weights);
+
+    let unsigned = IndexingConvention {
+        signed_weights: false,
+        ..kaneko.clone()
+    };
+    let magnitudes = remap(&exported, &kaneko, &unsigned).unwrap();
+    assert!(magnitudes.iter().all(|w| *w >= 0.0)); // type-sign constraint stripped
+
+    let back = remap(&remap(&magnitudes, &unsigned, &kaneko).unwrap(), &kaneko, &native).unwrap();
+    assert_eq!(back, weights);
+}
+
+#[test]
+fn test_import_kaneko_c_layout_xor() {
+    // 2 inputs, hidden OR/AND detectors, output = OR and not AND; indices follow the C reference:
+    // 0 bias+, 1 bias-, 2 x1+, 3 x1-, 4 x2+, 5 x2-, 6 output, 7 hidden OR, 8 hidden AND
+    let dimensions = NetworkDimensions::new(2, 2, 1);
+    let n = dimensions.total_neurons;
+    let mut fixture = vec![0.0; n * n];
+    let mut set = |to: usize, from: usize, weight: f64| fixture[to * n + from] = weight;
+
+    set(7, 0, -1.25);
+    set(7, 2, 2.0);
+    set(7, 4, 2.0);
+    set(8, 0, -3.75);
+    set(8, 2, 2.0);
+    set(8, 4, 2.0);
+    set(6, 0, -1.25);
+    set(6, 7, 2.0);
+    set(6, 8, -2.0);
+
+    let mut network = EDNetwork::new(dimensions.clone(), NetworkConfig::default()).unwrap();
+    network.import_weights(&fixture, &IndexingConvention::kaneko_c(dimensions)).unwrap();
+
+    for pattern in TrainingPattern::create_xor_dataset() {
+        let output = network.forward(&pattern.inputs).unwrap()[0];
+        assert!((output - pattern.targets[0]).abs() < 0.1, "pattern {} gave {}", pattern.id, output);
+    }
+}
+
+#[test]
+fn test_train_xor_converges() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 16, 1), NetworkConfig::default()).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+
+    let stats = network.train(1000).unwrap();
+    assert!(stats.converged, "did not converge: {}", stats);
+    assert_eq!(stats.error_count, 0);
+    assert_eq!(stats.error_history.len(), stats.epoch);
+
+    for pattern in TrainingPattern::create_xor_dataset() {
+        let output = network.forward(&pattern.inputs).unwrap()[0];
+        assert!((output - pattern.targets[0]).abs() < 0.1);
+    }
+}
+
+#[test]
+fn test_train_pattern_reduces_error() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    let pattern = TrainingPattern::new(vec![1.0, 0.0], vec![1.0], 0);
+
+    let output = network.forward(&pattern.inputs).unwrap()[0];
+    let first = network.train_pattern(&pattern).unwrap();
+    assert!((first - (1.0 - output)).abs() < 1e-12);
+
+    let mut last = first;
+    for _ in 0..20 {
+        last = network.train_pattern(&pattern).unwrap();
+    }
+    assert!(last < first);
+
+    let wrong = TrainingPattern::new(vec![1.0, 0.0], vec![1.0, 0.0], 1);
+    assert_eq!(
+        network.train_pattern(&wrong),
+        Err(EdError::DimensionMismatch {
+            expected: 1,
+            got: 2
+        })
+    );
+}
+
+#[test]
+fn test_train_xor_weight_decrement_mode() {
+    let config = NetworkConfig {
+        mode_weight_decrement: true,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 16, 1), config).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+
+    assert!(network.train(1000).unwrap().converged);
+
+    // With a fixed seed the flag alone changes how fast XOR is learned
+    let epochs = |mode_weight_decrement| {
+        let config = NetworkConfig {
+            mode_weight_decrement,
+            ..NetworkConfig::default()
+        };
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 8, 1), config, 21).unwrap();
+        network.training_data = TrainingPattern::create_xor_dataset();
+        let stats = network.train(2000).unwrap();
+        assert!(stats.converged, "{}", mode_weight_decrement);
+        stats.epoch
+    };
+    assert_ne!(epochs(false), epochs(true));
+}
+
+#[test]
+fn test_train_epoch_management() {
+    let config = NetworkConfig {
+        convergence_threshold: 0.0, // never converges
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), config).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+
+    let stats = network.train(3).unwrap();
+    assert_eq!(stats.epoch, 3);
+    assert!(!stats.converged);
+
+    network.train(2).unwrap();
+    assert_eq!(network.stats().epoch, 5);
+    assert_eq!(network.stats().error_history.len(), 5);
+    assert_eq!(network.stats().pattern_count, 4);
+}
+
+#[derive(Clone)]
+struct ScaleHook(f64);
+
+impl ConnectionUpdateHook for ScaleHook {
+    fn adjust_delta(&mut self, ctx: UpdateContext) -> f64 {
+        ctx.proposed_delta * self.0
+    }
+}
+
+fn weights(network: &EDNetwork) -> Vec<f64> {
+    network.connections.iter().flatten().map(|c| c.weight).collect()
+}
+
+#[test]
+fn test_update_hook_halves_deltas() {
+    let mut plain = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    let mut hooked = plain.clone();
+    hooked.set_update_hook(ScaleHook(0.5));
+
+    let initial = weights(&plain);
+    let pattern = TrainingPattern::new(vec![1.0, 0.0], vec![1.0], 0);
+    plain.train_pattern(&pattern).unwrap();
+    hooked.train_pattern(&pattern).unwrap();
+
+    for ((start, full), half) in initial.iter().zip(weights(&plain)).zip(weights(&hooked)) {
+        assert!(((half - start) - 0.5 * (full - start)).abs() < 1e-12);
+    }
+    assert!(plain.is_replayable());
+    assert!(!hooked.is_replayable());
+
+    // A dry run commits nothing, so it leaves a hooked network replayable
+    let mut checked = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    checked.set_update_hook(ScaleHook(0.5));
+    checked.dry_run(&[pattern]).unwrap();
+    assert!(checked.is_replayable());
+}
+
+#[test]
+fn test_update_hook_veto_freezes_network() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    network.set_update_hook(ScaleHook(0.0));
+    network.training_data = TrainingPattern::create_xor_dataset();
+
+    let initial = weights(&network);
+    network.train(10).unwrap();
+
+    assert_eq!(weights(&network), initial);
+}
+
+#[test]
+fn test_predict_matches_forward_without_mutation() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    network.train(20).unwrap();
+
+    let inputs: Vec<Vec<f64>> = network.training_data.iter().map(|p| p.inputs.clone()).collect();
+    let expected: Vec<Vec<f64>> = inputs.iter().map(|input| network.forward(input).unwrap()).collect();
+
+    let shared = std::sync::Arc::new(network);
+    let worker = {
+        let shared = shared.clone();
+        let inputs = inputs.clone();
+        std::thread::spawn(move || shared.predict_batch(&inputs).unwrap())
+    };
+
+    assert_eq!(worker.join().unwrap(), expected);
+    // Reused buffers give the same outputs as fresh ones
+    let mut buffers = PredictBuffers::default();
+    for (input, expected) in inputs.iter().zip(&expected) {
+        assert_eq!(shared.predict_into(input, &mut buffers).unwrap(), expected.as_slice());
+    }
+    assert_eq!(
+        shared.predict(&[0.0]),
+        Err(EdError::DimensionMismatch {
+            expected: 2,
+            got: 1
+        })
+    );
+}
+
+#[test]
+fn test_builder_constructs_network() {
+    let network = EDNetworkBuilder::new()
+        .inputs(2)
+        .hidden(4)
+        .outputs(1)
+        .learning_rate(0.5)
+        .timesteps(3)
+        .build()
+        .unwrap();
+
+    assert_eq!(network.dimensions, NetworkDimensions::new(2, 4, 1));
+    assert_eq!(network.config.learning_rate, 0.5);
+    assert_eq!(network.config.timesteps, 3);
+    assert_eq!(network.config.bias, NetworkConfig::default().bias);
+
+    assert!(matches!(
+        EDNetworkBuilder::new().inputs(2).hidden(4).build(),
+        Err(EdError::InvalidConfig(_))
+    ));
+    assert!(matches!(
+        EDNetworkBuilder::new().inputs(500).hidden(10).outputs(1).build(),
+        Err(EdError::NetworkTooLarge { .. })
+    ));
+}
+
+#[test]
+fn test_preflight_clean_pairing() {
+    let builder = EDNetworkBuilder::new().inputs(2).hidden(16).outputs(1);
+    let report = preflight(&builder, &TrainingPattern::create_xor_dataset());
+
+    assert!(report.is_clean(), "{}", report);
+}
+
+#[test]
+fn test_preflight_planted_problems() {
+    let xor = TrainingPattern::create_xor_dataset();
+    let builder = EDNetworkBuilder::new().inputs(2).hidden(16).outputs(1);
+    let codes = |builder: &EDNetworkBuilder, dataset: &[TrainingPattern]| preflight(builder, dataset).codes();
+
+    assert_eq!(codes(&builder, &[]), vec![FindingCode::EmptyDataset]);
+    assert_eq!(
+        codes(&builder.clone().inputs(3).hidden(16), &xor),
+        vec![FindingCode::InputSizeMismatch]
+    );
+    assert_eq!(codes(&builder.clone().outputs(2), &xor), vec![FindingCode::TargetSizeMismatch]);
+    assert_eq!(codes(&builder.clone().hidden(2), &xor), vec![FindingCode::HiddenTooSmall]);
+    assert_eq!(
+        codes(&builder.clone().convergence_threshold(1e-6), &xor),
+        vec![FindingCode::ConvergenceUnreachable]
+    );
+
+    let mut targets = xor.clone();
+    targets[1].targets[0] = 2.0;
+    assert_eq!(codes(&builder, &targets), vec![FindingCode::TargetOutOfRange]);
+
+    let mut inputs = xor.clone();
+    inputs[1].inputs[0] = 255.0;
+    assert_eq!(codes(&builder, &inputs), vec![FindingCode::InputOutOfRange]);
+
+    let mut conflicting = xor.clone();
+    conflicting.push(TrainingPattern::new(vec![1.0, 1.0], vec![1.0], 4));
+    assert_eq!(codes(&builder, &conflicting), vec![FindingCode::ConflictingPatterns]);
+
+    let mut non_finite = xor.clone();
+    non_finite[0].inputs[0] = f64::NAN;
+    assert_eq!(codes(&builder, &non_finite)[0], FindingCode::NonFiniteValue);
+
+    let constant: Vec<TrainingPattern> = xor
+        .iter()
+        .map(|p| TrainingPattern::new(vec![p.inputs[0], 1.0], vec![p.inputs[0]], p.id))
+        .collect();
+    let report = preflight(&builder, &constant);
+    assert!(report.codes().contains(&FindingCode::ConstantInput));
+    assert!(!report.has_errors());
+}
+
+#[test]
+fn test_preflight_target_range_follows_output_activation() {
+    let xor = TrainingPattern::create_xor_dataset();
+    let signed: Vec<TrainingPattern> = xor
+        .iter()
+        .map(|p| TrainingPattern::new(p.inputs.clone(), vec![2.0 * p.targets[0] - 1.0], p.id))
+        .collect();
+    let regression: Vec<TrainingPattern> = xor
+        .iter()
+        .map(|p| TrainingPattern::new(p.inputs.clone(), vec![10.0 * p.targets[0] - 5.0], p.id))
+        .collect();
+    let builder = |activation: Activation, output_activation: Option<Activation>| {
+        let config = NetworkConfig {
+            activation,
+            output_activation,
+            ..NetworkConfig::default()
+        };
+        EDNetworkBuilder::new().inputs(2).hidden(16).outputs(1).config(config)
+    };
+    let codes = |builder: &EDNetworkBuilder, dataset: &[TrainingPattern]| preflight(builder, dataset).codes();
+
+    let sigmoid = builder(Activation::Sigmoid, None);
+    assert_eq!(codes(&sigmoid, &signed), vec![FindingCode::TargetOutOfRange]);
+    assert!(codes(&builder(Activation::Tanh, None), &signed).is_empty());
+    assert!(codes(&builder(Activation::Sigmoid, Some(Activation::Tanh)), &signed).is_empty());
+    assert_eq!(
+        codes(&builder(Activation::Tanh, None), &regression),
+        vec![FindingCode::TargetOutOfRange]
+    );
+    assert!(codes(&builder(Activation::Sigmoid, Some(Activation::Linear)), &regression).is_empty());
+    assert_eq!(
+        codes(&builder(Activation::ReLU, None), &signed),
+        vec![FindingCode::TargetOutOfRange]
+    );
+
+    // The output activation decides the range, not the hidden one
+    assert_eq!(
+        codes(&builder(Activation::Linear, Some(Activation::Sigmoid)), &signed),
+        vec![FindingCode::TargetOutOfRange]
+    );
+    let message = &preflight(&sigmoid, &signed).findings[0].message;
+    assert!(message.contains("[0, 1]") && message.contains("sigmoid"), "{}", message);
+}
+
+#[test]
+fn test_network_builder_validation() {
+    let config = NetworkConfig {
+        timesteps: 3,
+        ..NetworkConfig::default()
+    };
+    let network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(4)
+        .outputs(1)
+        .config(config)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    assert_eq!(network.config.timesteps, 3);
+    assert_eq!(network.training_data.len(), 4);
+    assert_eq!(network.stats.pattern_count, 4);
+
+    let zero_hidden = NetworkBuilder::new().inputs(2).outputs(1).build();
+    assert!(matches!(zero_hidden, Err(EdError::InvalidConfig(_))));
+
+    let mismatched = NetworkBuilder::new()
+        .inputs(3)
+        .hidden(4)
+        .outputs(1)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build();
+    assert!(matches!(
+        mismatched,
+        Err(EdError::DimensionMismatch {
+            expected: 3,
+            got: 2
+        })
+    ));
+}
+
+#[test]
+fn test_errors_from_misuse() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+
+    assert_eq!(
+        network.forward(&[1.0]),
+        Err(EdError::DimensionMismatch {
+            expected: 2,
+            got: 1
+        })
+    );
+    assert_eq!(network.train(10).err(), Some(EdError::EmptyTrainingSet));
+
+    let flat = NetworkConfig {
+        sigmoid_steepness: 0.0,
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(2, 4, 1), flat),
+        Err(EdError::InvalidConfig(msg)) if msg.contains("sigmoid_steepness")
+    ));
+
+    // Stats for an empty dataset stay finite instead of dividing by zero
+    let mut stats = network.stats.clone();
+    stats.update_epoch(1, 0.0, 0);
+    assert_eq!((stats.accuracy, stats.error_rate()), (0.0, 0.0));
+}
+
+#[test]
+fn test_order_recorder_replays_seeded_shuffles() {
+    let mut rng = StdRng::seed_from_u64(7);
+    let mut recorder = OrderRecorder::new(20);
+    let mut order: Vec<usize> = (0..20).collect();
+    for epoch in 1..=30 {
+        order.shuffle(&mut rng);
+        recorder.record(epoch, &order).unwrap();
+    }
+
+    let mut replay = StdRng::seed_from_u64(7);
+    let mut expected: Vec<usize> = (0..20).collect();
+    for (epoch, recorded) in recorder.iter() {
+        expected.shuffle(&mut replay);
+        let mut sorted = recorded.clone();
+        sorted.sort_unstable();
+        assert_eq!(sorted, (0..20).collect::<Vec<_>>());
+        assert_eq!(recorded, expected, "epoch {}", epoch);
+    }
+    assert_eq!(recorder.epochs().len(), 30);
+    assert!(recorder.to_csv().lines().nth(1).unwrap().starts_with("1,"));
+
+    assert!(matches!(recorder.record(31, &[0; 20]), Err(EdError::InvalidConfig(_))));
+    assert!(matches!(recorder.record(31, &[0, 1]), Err(EdError::DimensionMismatch { .. })));
+}
+
+#[test]
+fn test_order_recorder_memory_cap() {
+    let cap = 4096;
+    let mut recorder = OrderRecorder::with_memory_cap(100, cap);
+    let order: Vec<usize> = (0..100).rev().collect();
+    for epoch in 1..=100_000 {
+        recorder.record(epoch, &order).unwrap();
+        assert!(recorder.memory_bytes() <= cap);
+    }
+
+    let epochs = recorder.epochs();
+    assert_eq!(epochs[0], 1);
+    assert!(recorder.stride() > 1);
+    assert!(epochs.iter().all(|epoch| (epoch - 1) % recorder.stride() == 0));
+}
+
+#[test]
+fn test_order_correlation_on_constructed_data() {
+    // Pattern p is always shown at position p and solved at epoch p + 1
+    let mut recorder = OrderRecorder::new(4);
+    let mut curves = PatternCurveRecorder::new(4);
+    for epoch in 1..=5 {
+        recorder.record(epoch, &[0, 1, 2, 3]).unwrap();
+        let errors: Vec<f64> = (0..4)
+            .map(|p| {
+                if epoch > p {
+                    0.1
+                } else {
+                    0.9
+                }
+            })
+            .collect();
+        curves.record(epoch, &errors).unwrap();
+    }
+
+    let report = order_correlation_within(&recorder, &curves, 3);
+    assert_eq!(report.early_epochs, 3);
+    assert_eq!(report.patterns[2].mean_position, 2.0);
+    assert_eq!(report.patterns[2].solved_epoch, Some(3));
+    assert!((report.correlation.unwrap() - 1.0).abs() < 1e-12);
+
+    // Reversed presentation order gives perfect anti-correlation
+    let mut reversed = OrderRecorder::new(4);
+    for epoch in 1..=5 {
+        reversed.record(epoch, &[3, 2, 1, 0]).unwrap();
+    }
+    assert!((order_correlation_within(&reversed, &curves, 3).correlation.unwrap() + 1.0).abs() < 1e-12);
+}
+
+#[test]
+fn test_save_load_json_round_trip() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    network.train(5).unwrap();
+
+    let path = std::env::temp_dir().join(format!("edla_round_trip_{}.json", std::process::id()));
+    network.save_json(&path).unwrap();
+    let loaded = EDNetwork::load_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(weights(&loaded), weights(&network));
+    assert_eq!(loaded.stats.epoch, 5);
+    assert_eq!(loaded.config.timesteps, network.config.timesteps);
+    for pattern in &network.training_data {
+        assert_eq!(loaded.predict(&pattern.inputs).unwrap(), network.predict(&pattern.inputs).unwrap());
+    }
+}
+
+#[test]
+fn test_save_load_json_after_train_pattern() {
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 8, 1), NetworkConfig::default(), 3).unwrap();
+    for pattern in TrainingPattern::create_xor_dataset() {
+        network.train_pattern(&pattern).unwrap();
+    }
+
+    // `online.patterns` is keyed by pattern id, which JSON writes as string keys
+    let path = std::env::temp_dir().join(format!("edla_online_round_trip_{}.json", std::process::id()));
+    network.save_json(&path).unwrap();
+    let loaded = EDNetwork::load_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert_eq!(loaded.stats.online, network.stats.online);
+    assert_eq!(loaded.stats.online.pattern_count, 4);
+    assert_eq!(weights(&loaded), weights(&network));
+
+    let keyed: std::collections::BTreeMap<u8, String> = json::from_str(r#"{"7":"seven"}"#).unwrap();
+    assert_eq!(keyed[&7], "seven");
+    assert!(json::from_str::<std::collections::BTreeMap<u8, String>>(r#"{"x":"y"}"#).is_err());
+    let named: std::collections::BTreeMap<String, u8> = json::from_str(r#"{"7":7}"#).unwrap();
+    assert_eq!(named["7"], 7);
+}
+
+#[test]
+fn test_load_json_rejects_bad_files() {
+    let dir = std::env::temp_dir();
+    let truncated = dir.join(format!("edla_truncated_{}.json", std::process::id()));
+    let garbage = dir.join(format!("edla_garbage_{}.json", std::process::id()));
+
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+    network.connections.pop();
+    network.save_json(&truncated).unwrap();
+    std::fs::write(&garbage, "{\"layers\": [").unwrap();
+
+    let missing_row = EDNetwork::load_json(&truncated);
+    let parse_error = EDNetwork::load_json(&garbage);
+    std::fs::remove_file(&truncated).unwrap();
+    std::fs::remove_file(&garbage).unwrap();
+
+    assert_eq!(
+        missing_row.err(),
+        Some(EdError::DimensionMismatch {
+            expected: 11,
+            got: 10
+        })
+    );
+    assert!(matches!(parse_error, Err(EdError::SerializationError(_))));
+    match EDNetwork::load_json(dir.join("edla_missing.json")) {
+        Err(EdError::Io(err)) => assert_eq!(err.kind(), std::io::ErrorKind::NotFound),
+        other => panic!("expected I/O error, got {:?}", other),
+    }
+}
+
+#[cfg(feature = "binary")]
+#[test]
+fn test_save_load_bin_round_trip() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 16, 1), NetworkConfig::default()).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    network.train(100).unwrap();
+
+    let path = std::env::temp_dir().join(format!("edla_round_trip_{}.bin", std::process::id()));
+    network.save_bin(&path).unwrap();
+    let loaded = EDNetwork::load_bin(&path);
+
+    // Bump the version field to simulate a file from a newer release
+    let mut bytes = std::fs::read(&path).unwrap();
+    assert_eq!(&bytes[..4], b"EDLA");
+    bytes[4] = 99;
+    std::fs::write(&path, &bytes).unwrap();
+    let newer = EDNetwork::load_bin(&path);
+    std::fs::remove_file(&path).unwrap();
+
+    let loaded = loaded.unwrap();
+    assert!(loaded.stats.converged);
+    assert_eq!(weights(&loaded), weights(&network));
+    assert_eq!(
+        newer.err(),
+        Some(EdError::VersionMismatch {
+            expected: 34,
+            found: 99
+        })
+    );
+}
+
+#[test]
+fn test_seeded_construction_is_reproducible() {
+    let build = |seed| NetworkBuilder::new().inputs(2).hidden(8).outputs(1).seed(seed).build().unwrap();
+    let (a, b, c) = (build(42), build(42), build(43));
+
+    let bits = |network: &EDNetwork| weights(network).iter().map(|w| w.to_bits()).collect::<Vec<u64>>();
+    assert_eq!(bits(&a), bits(&b));
+    assert_ne!(bits(&a), bits(&c));
+    assert_eq!(a.seed, Some(42));
+
+    let mut rng = StdRng::seed_from_u64(42);
+    let injected = EDNetwork::new_with_rng(NetworkDimensions::new(2, 8, 1), NetworkConfig::default(), &mut rng).unwrap();
+    assert_eq!(bits(&injected), bits(&a));
+    assert_eq!(injected.seed, None);
+
+    let seeded = EDNetwork::new_seeded(NetworkDimensions::new(2, 8, 1), NetworkConfig::default(), 42).unwrap();
+    assert_eq!(bits(&seeded), bits(&a));
+    assert_eq!(seeded.seed, Some(42));
+
+    let path = std::env::temp_dir().join(format!("edla_seed_{}.json", std::process::id()));
+    a.save_json(&path).unwrap();
+    let loaded = EDNetwork::load_json(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loaded.unwrap().seed, Some(42));
+}
+
+#[test]
+fn test_shuffled_epochs_are_reproducible() {
+    let build = |shuffle_patterns| {
+        NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(NetworkConfig {
+                shuffle_patterns,
+                ..NetworkConfig::default()
+            })
+            .seed(5)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap()
+    };
+    let bits = |network: &EDNetwork| weights(network).iter().map(|w| w.to_bits()).collect::<Vec<u64>>();
+
+    let mut a = build(true);
+    let mut b = build(true);
+    let mut fixed = build(false);
+    for _ in 0..20 {
+        a.train_epoch().unwrap();
+        b.train_epoch().unwrap();
+        fixed.train_epoch().unwrap();
+    }
+    assert_eq!(bits(&a), bits(&b));
+    assert_ne!(bits(&a), bits(&fixed));
+    assert_eq!(a.stats.pattern_count, 4);
+    assert_eq!(a.stats.epoch, 20);
+
+    // The shuffle RNG state survives a save, so a resumed run matches an uninterrupted one
+    let path = std::env::temp_dir().join(format!("edla_shuffle_{}.json", std::process::id()));
+    a.save_json(&path).unwrap();
+    let mut resumed = EDNetwork::load_json(&path).unwrap();
+    std::fs::remove_file(&path).unwrap();
+    for _ in 0..10 {
+        a.train_epoch().unwrap();
+        resumed.train_epoch().unwrap();
+    }
+    assert_eq!(bits(&resumed), bits(&a));
+}
+
+#[test]
+fn test_capacity_report_on_overprovisioned_xor() {
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(16)
+        .outputs(1)
+        .seed(3)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    network.train(200).unwrap();
+    let dataset = TrainingPattern::create_xor_dataset();
+    let before = weights(&network);
+
+    let report = capacity_report(&network, &dataset).unwrap();
+    assert_eq!(weights(&network), before); // input network untouched
+    assert_eq!(report.trained_hidden, 16);
+    assert!(report.sufficient_hidden <= 16);
+    assert_eq!(report.sufficient_hidden + report.removal_order.len(), 16);
+    assert_eq!(report.removal_order.len(), report.accuracy_trajectory.len());
+
+    let pruned = report.prune(&network);
+    let correct = dataset
+        .iter()
+        .filter(|p| (pruned.predict(&p.inputs).unwrap()[0] - p.targets[0]).abs() <= 0.5)
+        .count();
+    let expected = report.accuracy_trajectory.last().copied().unwrap_or(report.baseline_accuracy);
+    assert_eq!(100.0 * correct as f64 / 4.0, expected);
+}
+
+#[test]
+fn test_activation_functions() {
+    assert_eq!(Activation::Sigmoid.apply(0.3, 0.4), sigmoid(0.3, 0.4));
+    assert_eq!(Activation::Tanh.apply(0.2, 0.4), 1.0_f64.tanh());
+    assert_eq!(Activation::ReLU.apply(-2.0, 0.4), 0.0);
+    assert_eq!(Activation::LeakyReLU(0.1).apply(-2.0, 0.4), -0.2);
+    assert_eq!(Activation::Tanh.derivative(0.5), 0.75);
+    assert_eq!(Activation::LeakyReLU(0.1).derivative(-0.2), 0.1);
+
+    // `activate` keeps its sigmoid default; `activate_as` takes the activation
+    let mut neuron = Neuron::new(NeuronType::Excitatory, 0);
+    neuron.input = 0.2;
+    neuron.activate(0.4);
+    assert_eq!(neuron.output, sigmoid(0.2, 0.4));
+    neuron.activate_as(Activation::Tanh, 0.4);
+    assert_eq!(neuron.output, 1.0_f64.tanh());
+
+    // The configured activation drives the forward pass
+    let config = NetworkConfig {
+        activation: Activation::Tanh,
+        timesteps: 1,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), config).unwrap();
+    let output = network.forward(&[1.0, 0.0]).unwrap()[0];
+    // Hidden neurons are still silent after a single step, so only bias and inputs reach the output
+    let sources: Vec<f64> = network.layers.iter().flat_map(|l| &l.neurons).take(6).map(|n| n.output).collect();
+    let sum: f64 = sources
+        .iter()
+        .enumerate()
+        .map(|(from, output)| output * network.connections[from][10].weight)
+        .sum();
+    assert!((output - tanh_activation(sum, 0.4)).abs() < 1e-12);
+    assert_eq!(NetworkConfig::default().activation, Activation::Sigmoid);
+    assert_eq!(NetworkConfig::default().activation_for(LayerType::Output), Activation::Sigmoid);
+}
+
+#[test]
+fn test_output_activation_override() {
+    assert_eq!(Activation::Linear.apply(-2.5, 0.4), -2.5);
+    assert_eq!(Activation::Linear.derivative(7.0), 1.0);
+
+    let config = NetworkConfig {
+        output_activation: Some(Activation::Linear),
+        timesteps: 1,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), config).unwrap();
+    let output = network.forward(&[1.0, 0.0]).unwrap()[0];
+    let sum: f64 = network
+        .layers
+        .iter()
+        .flat_map(|l| &l.neurons)
+        .take(6)
+        .enumerate()
+        .map(|(from, neuron)| neuron.output * network.connections[from][10].weight)
+        .sum();
+    assert!((output - sum).abs() < 1e-12);
+    // Hidden neurons keep the shared activation
+    assert_eq!(network.config.activation_for(LayerType::Hidden), Activation::Sigmoid);
+
+    let restored: EDNetwork = json::from_str(&json::to_string(&network).unwrap()).unwrap();
+    assert_eq!(restored.config.output_activation, Some(Activation::Linear));
+}
+
+#[test]
+fn test_training_session_resumes_exactly() {
+    let build = || {
+        NetworkBuilder::new()
+            .inputs(3)
+            .hidden(6)
+            .outputs(1)
+            .seed(11)
+            .training_data(TrainingPattern::create_parity_dataset(3))
+            .build()
+            .unwrap()
+    };
+    let options = TrainingOptions {
+        stopping: StoppingCriteria::epochs(20),
+    };
+
+    let mut chunked = TrainingSession::new(build(), options.clone());
+    chunked.advance(10).unwrap();
+    assert_eq!(chunked.epochs_run(), 10);
+
+    // Persist mid-run and continue from the restored copy
+    let saved = json::to_string(&chunked).unwrap();
+    let mut resumed: TrainingSession = json::from_str(&saved).unwrap();
+    chunked.advance(10).unwrap();
+    resumed.advance(10).unwrap();
+
+    let mut whole = TrainingSession::new(build(), options);
+    let status = whole.advance(20).unwrap();
+    assert_ne!(status, SessionStatus::Running);
+    assert_eq!(whole.advance(5).unwrap(), status); // budget is spent or training converged
+
+    let bits = |session: &TrainingSession| weights(session.network()).iter().map(|w| w.to_bits()).collect::<Vec<u64>>();
+    assert_eq!(bits(&chunked), bits(&whole));
+    assert_eq!(bits(&resumed), bits(&whole));
+    assert_eq!(chunked.stats().error_history, whole.stats().error_history);
+
+    let (network, outcome) = whole.finish();
+    assert_eq!(outcome.epochs, network.stats.epoch);
+    assert_eq!(outcome.status, status);
+}
+
+#[test]
+fn test_train_pattern_online_stats() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 8, 1), NetworkConfig::default()).unwrap();
+    let xor = TrainingPattern::create_xor_dataset();
+
+    let first = network.train_pattern(&xor[0]).unwrap();
+    assert_eq!(network.stats.online.pattern_count, 1);
+    assert_eq!(network.stats.online.total_error, first);
+
+    network.train_pattern(&xor[1]).unwrap();
+    let repeat = network.train_pattern(&xor[0]).unwrap();
+    assert_eq!(network.stats.online.pattern_count, 2); // ids already seen are not counted twice
+    assert!((network.stats.online.total_error - (repeat + network.stats.online.patterns[&1].0)).abs() < 1e-12);
+    assert!(network.training_data.is_empty());
+
+    for _ in 0..300 {
+        for pattern in &xor {
+            network.train_pattern(pattern).unwrap();
+        }
+    }
+    assert_eq!(network.stats.online.pattern_count, 4);
+    assert_eq!(network.stats.online.accuracy, 100.0);
+    assert_eq!(network.stats.epoch, 0);
+}
+
+#[test]
+fn test_train_pattern_mixed_with_train_epoch() {
+    let config = NetworkConfig {
+        weight_clamp: Some(0.5),
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 8, 1), config, 5).unwrap();
+    let xor = TrainingPattern::create_xor_dataset();
+    network.training_data = xor.clone();
+
+    network.train(3).unwrap();
+    let clipped = network.stats.clipped_updates;
+    network.train_pattern(&TrainingPattern::new(vec![1.0, 1.0], vec![1.0], 99)).unwrap();
+    let online = network.stats.online.clone();
+    assert_eq!(online.pattern_count, 1);
+    assert_eq!(network.stats.pattern_count, 4); // the epoch figures ignore online calls
+
+    network.train_epoch().unwrap();
+    assert_eq!(network.stats.epoch, 4); // the epoch count is never reset implicitly
+    assert_eq!(network.stats.error_history.len(), 4);
+    assert!(network.stats.clipped_updates >= clipped);
+    assert_eq!(network.stats.online, online); // nor does epoch training touch the online figures
+
+    network.training_data.pop();
+    network.train_epoch().unwrap();
+    assert_eq!(network.stats.epoch, 5);
+    assert_eq!(network.stats.pattern_count, 3);
+}
+
+#[test]
+fn test_ensemble_aggregation_weights() {
+    let xor = TrainingPattern::create_xor_dataset();
+    let trained = |seed| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(16)
+            .outputs(1)
+            .seed(seed)
+            .training_data(xor.clone())
+            .build()
+            .unwrap();
+        network.train(200).unwrap();
+        network
+    };
+    let mut sabotaged = trained(2);
+    for connection in sabotaged.connections.iter_mut().flatten() {
+        connection.weight = 0.0;
+    }
+
+    let mut ensemble = EDEnsemble::new(vec![trained(1), sabotaged, trained(3)]).unwrap();
+    let aggregation = ensemble.fit_aggregation_weights(&xor, AggregationScheme::default()).unwrap();
+    assert!(aggregation.weights[1] < 1e-3);
+    assert!((aggregation.weights.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(ensemble.to_string().contains("member 1: weight 0.0"));
+
+    let error = |predict: &dyn Fn(&[f64]) -> Vec<f64>| xor.iter().map(|p| (predict(&p.inputs)[0] - p.targets[0]).abs()).sum::<f64>();
+    let plain = error(&|inputs| ensemble.predict(inputs).unwrap());
+    let weighted = error(&|inputs| ensemble.predict_weighted(inputs).unwrap());
+    assert!(weighted < plain);
+
+    // Identical members share the weight evenly under either scheme
+    let member = trained(4);
+    let mut clones = EDEnsemble::new(vec![member.clone(), member.clone(), member]).unwrap();
+    for scheme in [AggregationScheme::default(), AggregationScheme::InverseError] {
+        let weights = &clones.fit_aggregation_weights(&xor, scheme).unwrap().weights;
+        assert!(weights.iter().all(|w| (w - 1.0 / 3.0).abs() < 1e-12));
+    }
+}
+
+#[test]
+fn test_ensemble_modes_and_member_cap() {
+    let xor = TrainingPattern::create_xor_dataset();
+    let trained = |seed| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(16)
+            .outputs(1)
+            .seed(seed)
+            .training_data(xor.clone())
+            .build()
+            .unwrap();
+        network.train(200).unwrap();
+        network
+    };
+    // One member always answering 1 is outvoted by two that learned XOR
+    let mut stuck = trained(2);
+    for connection in stuck.connections.iter_mut().flatten() {
+        connection.weight = 0.0;
+    }
+    let bias = stuck.layer_range(LayerType::Bias).start;
+    for output in stuck.layer_range(LayerType::Output) {
+        stuck.connections[bias][output].weight = 10.0;
+    }
+
+    let ensemble = EDEnsemble::new(vec![trained(1), stuck, trained(3)]).unwrap();
+    for pattern in &xor {
+        let vote = ensemble.predict_ensemble(&pattern.inputs, EnsembleMode::MajorityVote).unwrap();
+        assert_eq!(vote, pattern.targets);
+        assert_eq!(
+            ensemble.predict_ensemble(&pattern.inputs, EnsembleMode::Average).unwrap(),
+            ensemble.predict(&pattern.inputs).unwrap()
+        );
+    }
+
+    let member = trained(1);
+    assert!(EDEnsemble::new(vec![member.clone(); MAX_OUTPUT_NETWORKS]).is_ok());
+    assert!(matches!(
+        EDEnsemble::new(vec![member; MAX_OUTPUT_NETWORKS + 1]),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_train_until_stopping_criteria() {
+    let build = || {
+        NetworkBuilder::new()
+            .inputs(2)
+            .hidden(16)
+            .outputs(1)
+            .seed(5)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap()
+    };
+
+    let mut network = build();
+    let stats = network.train_until(StoppingCriteria::epochs(3)).unwrap();
+    assert_eq!((stats.epoch, stats.stop_reason), (3, Some(StopReason::MaxEpochs)));
+
+    let mut network = build();
+    let stats = network
+        .train_until(StoppingCriteria::epochs(500).target_accuracy(100.0).target_error(0.0))
+        .unwrap();
+    assert_eq!(stats.stop_reason, Some(StopReason::TargetAccuracy));
+    assert_eq!(stats.accuracy, 100.0);
+    assert!(!stats.converged);
+
+    let mut network = build();
+    let stats = network.train_until(StoppingCriteria::epochs(500)).unwrap();
+    assert_eq!(stats.stop_reason, Some(StopReason::Converged));
+    assert!(stats.converged && stats.total_error < 0.1);
+
+    let mut network = build();
+    let stats = network
+        .train_until(
+            StoppingCriteria::epochs(500)
+                .target_error(0.0)
+                .max_duration(std::time::Duration::ZERO),
+        )
+        .unwrap();
+    assert_eq!((stats.epoch, stats.stop_reason), (0, Some(StopReason::Timeout)));
+}
+
+#[test]
+fn test_train_with_validation_restores_best() {
+    let parity = TrainingPattern::create_parity_dataset(4);
+    let (train, validation) = parity.split_at(12);
+    let mut network = NetworkBuilder::new()
+        .inputs(4)
+        .hidden(8)
+        .outputs(1)
+        .seed(9)
+        .training_data(train.to_vec())
+        .build()
+        .unwrap();
+
+    let stats = network.train_with_validation(validation, 5, 300).unwrap().clone();
+    assert_eq!(stats.validation_history.len(), stats.epoch);
+    let best = stats.best_epoch.unwrap();
+    let best_error = stats.validation_history[best - 1];
+    assert!(stats.validation_history.iter().all(|&error| error >= best_error));
+    if stats.stop_reason == Some(StopReason::ValidationPlateau) {
+        assert_eq!(stats.epoch, best + 5);
+    }
+
+    // The restored weights reproduce the best validation error
+    let restored: f64 = validation
+        .iter()
+        .map(|p| (network.predict(&p.inputs).unwrap()[0] - p.targets[0]).abs())
+        .sum();
+    assert!((restored - best_error).abs() < 1e-12);
+
+    assert_eq!(network.train_with_validation(&[], 5, 10).err(), Some(EdError::EmptyTrainingSet));
+
+    // Learned neuron thresholds are restored along with the weights
+    let config = NetworkConfig {
+        neuron_thresholds: true,
+        ..NetworkConfig::default()
+    };
+    let mut network = NetworkBuilder::new()
+        .inputs(4)
+        .hidden(8)
+        .outputs(1)
+        .config(config)
+        .seed(9)
+        .training_data(train.to_vec())
+        .build()
+        .unwrap();
+    let stats = network.train_with_validation(validation, 5, 300).unwrap().clone();
+    let best_error = stats.validation_history[stats.best_epoch.unwrap() - 1];
+    assert!(stats.epoch > stats.best_epoch.unwrap());
+    let restored: f64 = validation
+        .iter()
+        .map(|p| (network.predict(&p.inputs).unwrap()[0] - p.targets[0]).abs())
+        .sum();
+    assert!((restored - best_error).abs() < 1e-12);
+}
+
+#[test]
+fn test_train_with_validation_keeps_converged_weights() {
+    let xor = TrainingPattern::create_xor_dataset();
+    // Inverted targets make the first epoch the best validation epoch
+    let inverted: Vec<TrainingPattern> = xor
+        .iter()
+        .map(|p| TrainingPattern::new(p.inputs.clone(), vec![1.0 - p.targets[0]], p.id))
+        .collect();
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(8)
+        .outputs(1)
+        .seed(3)
+        .training_data(xor.clone())
+        .build()
+        .unwrap();
+
+    let stats = network.train_with_validation(&inverted, 1000, 1000).unwrap().clone();
+    assert_eq!(stats.stop_reason, Some(StopReason::Converged));
+    assert!(stats.best_epoch.unwrap() < stats.epoch);
+
+    // The model is the one the final epoch's figures were measured on
+    let validation_error: f64 = inverted
+        .iter()
+        .map(|p| (network.predict(&p.inputs).unwrap()[0] - p.targets[0]).abs())
+        .sum();
+    assert!((validation_error - stats.validation_history.last().unwrap()).abs() < 1e-12);
+    let correct = xor
+        .iter()
+        .filter(|p| (network.predict(&p.inputs).unwrap()[0] > 0.5) == (p.targets[0] > 0.5))
+        .count();
+    assert_eq!(correct as f64 / xor.len() as f64 * 100.0, stats.accuracy);
+}
+
+#[test]
+fn test_load_rejects_or_repairs_non_finite_weights() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 1), NetworkConfig::default()).unwrap();
+    network.connections[0][7].weight = f64::NAN;
+    network.connections[8][10].weight = f64::INFINITY;
+    let planted = RepairReport {
+        repaired: vec![(0, 7), (8, 10)],
+    };
+
+    let json_path = std::env::temp_dir().join(format!("edla_non_finite_{}.json", std::process::id()));
+    network.save_json(&json_path).unwrap();
+    let rejected = EDNetwork::load_json(&json_path);
+    let repaired = EDNetwork::load_json_with(&json_path, true);
+    std::fs::remove_file(&json_path).unwrap();
+
+    match rejected {
+        Err(EdError::NumericalInstability(msg)) => assert!(msg.contains("0->7") && msg.contains("8->10")),
+        other => panic!("expected non-finite weights to be rejected, got {:?}", other),
+    }
+    let (mut repaired, report) = repaired.unwrap();
+    assert_eq!(report, planted);
+    assert!(!repaired.connections[0][7].connection_enabled && repaired.connections[8][10].weight == 0.0);
+    assert!(repaired.forward(&[1.0, 0.0]).unwrap()[0].is_finite());
+    assert!(repaired.dry_run(&TrainingPattern::create_xor_dataset()).is_ok());
+
+    #[cfg(feature = "binary")]
+    {
+        let bin_path = std::env::temp_dir().join(format!("edla_non_finite_{}.bin", std::process::id()));
+        network.save_bin(&bin_path).unwrap();
+        let rejected = EDNetwork::load_bin(&bin_path);
+        let repaired = EDNetwork::load_bin_with(&bin_path, true);
+        std::fs::remove_file(&bin_path).unwrap();
+
+        assert!(matches!(rejected, Err(EdError::NumericalInstability(_))));
+        assert_eq!(repaired.unwrap().1, planted);
+    }
+
+    let convention = network.indexing_convention();
+    let mut flat = network.export_weights(&convention).unwrap();
+    flat[3] = f64::NAN;
+    assert!(matches!(
+        network.import_weights(&flat, &convention),
+        Err(EdError::NumericalInstability(_))
+    ));
+}
+
+#[test]
+fn test_softmax_output() {
+    let probabilities = softmax(&[1.0, 2.0, 3.0]);
+    assert!((probabilities.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+    assert!(probabilities.windows(2).all(|w| w[0] < w[1]));
+
+    // Large pre-activations do not overflow
+    let large = softmax(&[1000.0, 1000.0]);
+    assert_eq!(large, vec![0.5, 0.5]);
+
+    let config = NetworkConfig {
+        softmax_output: true,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 4, 3), config).unwrap();
+    let outputs = network.forward(&[1.0, 0.0]).unwrap();
+    let raw: Vec<f64> = network.layers[3].neurons.iter().map(|n| n.output).collect();
+    assert_eq!(outputs, softmax(&raw));
+    assert_eq!(network.predict(&[1.0, 0.0]).unwrap(), outputs);
+    assert_eq!(network.predict_into(&[1.0, 0.0], &mut PredictBuffers::default()).unwrap(), outputs);
+}
+
+#[test]
+fn test_metric_series_alignment_and_import() {
+    let coarse = MetricSeries::new("coarse", vec![1, 3, 5, 7], vec![1.0, 5.0, 3.0, 7.0]).unwrap();
+    let fine = MetricSeries::new("fine", (2..=8).collect(), vec![1.0; 7]).unwrap();
+
+    let interpolated = coarse.align(&fine, Alignment::Interpolate);
+    assert_eq!(interpolated.epochs, vec![2, 3, 4, 5, 6, 7]);
+    assert_eq!(interpolated.left, vec![3.0, 5.0, 4.0, 3.0, 5.0, 7.0]);
+    assert_eq!(interpolated.right, vec![1.0; 6]);
+
+    let truncated = coarse.align(&fine, Alignment::Truncate);
+    assert_eq!(truncated.epochs, vec![3, 5, 7]);
+    let difference = coarse.difference(&fine, Alignment::Truncate);
+    assert_eq!(difference.values(), &[4.0, 2.0, 6.0]);
+    assert_eq!(difference.name, "coarse - fine");
+    assert_eq!(coarse.ratio(&coarse, Alignment::Interpolate).values(), &[1.0; 4]);
+
+    assert!(MetricSeries::new("bad", vec![2, 1], vec![0.0, 0.0]).is_err());
+
+    let path = std::env::temp_dir().join(format!("edla_metric_{}.csv", std::process::id()));
+    std::fs::write(&path, "epoch,loss,acc\n1,0.9,50\n2,,60\n4,0.5,\n3,0.7,70\n").unwrap();
+    let loss = MetricSeries::from_csv(&path, "epoch", "loss").unwrap();
+    let missing = MetricSeries::from_csv(&path, "epoch", "lr");
+    std::fs::remove_file(&path).unwrap();
+    assert_eq!(loss.epochs(), &[1, 3, 4]);
+    assert_eq!(loss.values(), &[0.9, 0.7, 0.5]);
+    assert!((loss.value_at(2).unwrap() - 0.8).abs() < 1e-12);
+    assert!(matches!(missing, Err(EdError::InvalidConfig(_))));
+
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(4)
+        .outputs(1)
+        .seed(1)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    for _ in 0..5 {
+        network.train_epoch().unwrap();
+    }
+    let errors = MetricSeries::from_learning_stats(&network.stats, StatsField::Error);
+    assert_eq!(errors.epochs(), &[1, 2, 3, 4, 5]);
+    assert_eq!(errors.values(), network.stats.error_history.as_slice());
+}
+
+#[test]
+fn test_batch_update_mode_converges_on_xor() {
+    let trained = |update_mode| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(NetworkConfig {
+                update_mode,
+                ..NetworkConfig::default()
+            })
+            .seed(21)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        let first = {
+            network.train_epoch().unwrap();
+            weights(&network)
+        };
+        network.train(2000).unwrap();
+        (network, first)
+    };
+
+    let (online, online_first) = trained(UpdateMode::Online);
+    let (batch, batch_first) = trained(UpdateMode::Batch);
+    assert_ne!(online_first, batch_first);
+    for network in [&online, &batch] {
+        assert!(network.stats.converged, "{:?} did not converge", network.config.update_mode);
+        assert_eq!(network.stats.accuracy, 100.0);
+    }
+}
+
+#[test]
+fn test_batch_epoch_over_single_pattern_matches_online_update() {
+    let pattern = TrainingPattern::new(vec![1.0, 0.0], vec![1.0], 0);
+    let trained = |update_mode, epochs| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(4)
+            .outputs(1)
+            .config(NetworkConfig {
+                update_mode,
+                ..NetworkConfig::default()
+            })
+            .seed(9)
+            .training_data(vec![pattern.clone()])
+            .build()
+            .unwrap();
+        for _ in 0..epochs {
+            network.train_epoch().unwrap();
+        }
+        network
+    };
+
+    let online = trained(UpdateMode::Online, 1);
+    let batch = trained(UpdateMode::Batch, 1);
+    assert_ne!(weights(&online), weights(&trained(UpdateMode::Online, 0)));
+    assert_eq!(weights(&batch), weights(&online));
+    assert_eq!(batch.stats.total_error, online.stats.total_error);
+}
+
+#[test]
+fn test_connection_index_validation_and_repair() {
+    let mut network = NetworkBuilder::new().inputs(2).hidden(3).outputs(1).seed(4).build().unwrap();
+    assert!(network.check_invariants().is_ok());
+    let before = network.predict(&[1.0, 0.0]).unwrap();
+
+    // Transpose the labels of a hidden -> output connection, as a hand-built matrix might
+    let (hidden, output) = (6, 9);
+    let connection = &mut network.connections[hidden][output];
+    std::mem::swap(&mut connection.from, &mut connection.to);
+    assert!(matches!(network.check_invariants(), Err(EdError::InvalidTopology(_))));
+
+    let path = std::env::temp_dir().join(format!("edla_indices_{}.json", std::process::id()));
+    network.save_json(&path).unwrap();
+    let loaded = EDNetwork::load_json(&path);
+    std::fs::remove_file(&path).unwrap();
+    assert!(matches!(loaded, Err(EdError::InvalidTopology(_))));
+    assert!(network.import_weights(&weights(&network), &network.indexing_convention()).is_err());
+
+    assert_eq!(network.repair_connection_indices(), 1);
+    assert_eq!(
+        (network.connections[hidden][output].from, network.connections[hidden][output].to),
+        (hidden, output)
+    );
+    assert!(network.check_invariants().is_ok());
+    assert_eq!(network.repair_connection_indices(), 0);
+    assert_eq!(network.predict(&[1.0, 0.0]).unwrap(), before);
+}
+
+#[test]
+fn test_symmetric_weight_initialization() {
+    let mut rng = StdRng::seed_from_u64(8);
+    let draws: Vec<f64> = (0..1000).map(|_| random_weight_symmetric(&mut rng, 0.5)).collect();
+    assert!(draws.iter().all(|w| (-0.5..0.5).contains(w)));
+    assert!(draws.iter().any(|&w| w < 0.0) && draws.iter().any(|&w| w > 0.0));
+    assert!((0..1000).all(|_| (0.0..0.5).contains(&random_weight(&mut rng, 0.5))));
+
+    // Mean of U(-1, 1) is 0 with a standard error of about 0.006 over 10k draws
+    let mean = (0..10_000).map(|_| random_weight_symmetric(&mut rng, 1.0)).sum::<f64>() / 10_000.0;
+    assert!(mean.abs() < 0.03, "{}", mean);
+
+    // Count weights whose sign disagrees with the product of the endpoint type factors
+    let against_type = |symmetric_init| {
+        let config = NetworkConfig {
+            symmetric_init,
+            ..NetworkConfig::default()
+        };
+        let network = EDNetwork::new_seeded(NetworkDimensions::new(4, 8, 2), config, 12).unwrap();
+        let types: Vec<f64> = network
+            .layers
+            .iter()
+            .flat_map(|layer| layer.neurons.iter().map(|n| n.neuron_type.as_weight_factor()))
+            .collect();
+        network
+            .connections
+            .iter()
+            .flatten()
+            .filter(|c| c.connection_enabled && c.weight * types[c.from] * types[c.to] < 0.0)
+            .count()
+    };
+    assert_eq!(against_type(false), 0);
+    assert!(against_type(true) > 0);
+}
+
+#[test]
+fn test_mini_batch_of_one_matches_online() {
+    let run = |update_mode| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(6)
+            .outputs(1)
+            .config(NetworkConfig {
+                update_mode,
+                ..NetworkConfig::default()
+            })
+            .seed(17)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        for _ in 0..30 {
+            network.train_epoch().unwrap();
+        }
+        network
+    };
+    let bits = |network: &EDNetwork| weights(network).iter().map(|w| w.to_bits()).collect::<Vec<u64>>();
+
+    let online = run(UpdateMode::Online);
+    let single = run(UpdateMode::MiniBatch {
+        size: 1,
+    });
+    assert_eq!(bits(&single), bits(&online));
+    assert_eq!(single.stats.error_history, online.stats.error_history);
+
+    // Four patterns in batches of three leave a partial batch that must still be applied
+    let partial = run(UpdateMode::MiniBatch {
+        size: 3,
+    });
+    let full = run(UpdateMode::Batch);
+    assert_ne!(bits(&partial), bits(&online));
+    assert_ne!(bits(&partial), bits(&full));
+    assert_eq!(partial.stats.error_history.len(), 30);
+    assert_eq!(
+        run(UpdateMode::MiniBatch {
+            size: 4
+        })
+        .stats
+        .error_history,
+        full.stats.error_history
+    );
+
+    let config = NetworkConfig {
+        update_mode: UpdateMode::MiniBatch {
+            size: 0,
+        },
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(2, 2, 1), config),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_confusion_matrix_tallies_classes() {
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(8)
+        .outputs(1)
+        .seed(21)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    network.train(2000).unwrap();
+    let matrix = network.confusion_matrix(&TrainingPattern::create_xor_dataset()).unwrap();
+    assert_eq!(matrix.counts, vec![vec![2, 0], vec![0, 2]]);
+
+    let patterns: Vec<TrainingPattern> = (0..6)
+        .map(|id| {
+            let mut targets = vec![0.0; 3];
+            targets[id % 3] = 1.0;
+            TrainingPattern::new(vec![id as f64 / 6.0, 1.0], targets, id)
+        })
+        .collect();
+    let untrained = NetworkBuilder::new().inputs(2).hidden(4).outputs(3).seed(2).build().unwrap();
+    let matrix = untrained.confusion_matrix(&patterns).unwrap();
+    assert_eq!(matrix.classes(), 3);
+    assert!(matrix.counts.iter().all(|row| row.iter().sum::<usize>() == 2));
+
+    let grid = ConfusionMatrix {
+        counts: vec![vec![10, 0], vec![2, 8]],
+    };
+    assert_eq!(grid.to_string(), "    0  1\n 0 10  0\n 1  2  8");
+}
+
+#[test]
+fn test_error_history_csv_export() {
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(4)
+        .outputs(1)
+        .seed(3)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    network.train(3).unwrap();
+
+    let csv = network.stats.to_csv_string();
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines.len(), 4);
+    assert_eq!(lines[0], "epoch,error");
+    for (line, (epoch, error)) in lines[1..].iter().zip(network.stats.error_history.iter().enumerate()) {
+        assert_eq!(*line, format!("{},{}", epoch + 1, error));
+        assert_eq!(line.split(',').nth(1).unwrap().parse::<f64>().unwrap(), *error);
+    }
+
+    let path = std::env::temp_dir().join(format!("edla_history_{}.csv", std::process::id()));
+    network.stats.write_error_history_csv(&path).unwrap();
+    assert_eq!(std::fs::read_to_string(&path).unwrap(), csv);
+    std::fs::remove_file(&path).ok();
+    assert_eq!(LearningStats::default().to_csv_string(), "epoch,error\n");
+}
+
+#[test]
+fn test_dropout_silences_hidden_neurons_during_training_only() {
+    let config = NetworkConfig {
+        dropout_rate: 0.3,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 20, 1), config, 4).unwrap();
+    let hidden_outputs = |network: &EDNetwork| network.layers[2].neurons.iter().map(|n| n.output).collect::<Vec<f64>>();
+
+    let xor = TrainingPattern::create_xor_dataset();
+    let (mut dropped, mut total) = (0, 0);
+    for pattern in xor.iter().cycle().take(400) {
+        network.train_pattern(pattern).unwrap();
+        let outputs = hidden_outputs(&network);
+        dropped += outputs.iter().filter(|&&output| output == 0.0).count();
+        total += outputs.len();
+    }
+    let fraction = dropped as f64 / total as f64;
+    assert!((fraction - 0.3).abs() < 0.03, "{}", fraction);
+
+    network.forward(&xor[3].inputs).unwrap();
+    assert!(hidden_outputs(&network).iter().all(|&output| output > 0.0));
+
+    // A zero rate draws nothing from the RNG, so training is unchanged
+    let train = |config| {
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 8, 1), config, 21).unwrap();
+        network.training_data = TrainingPattern::create_xor_dataset();
+        network.train(50).unwrap();
+        weights(&network)
+    };
+    let with_rate = |dropout_rate| NetworkConfig {
+        dropout_rate,
+        ..NetworkConfig::default()
+    };
+    assert_eq!(train(with_rate(0.0)), train(NetworkConfig::default()));
+    assert_ne!(train(with_rate(0.2)), train(NetworkConfig::default()));
+
+    assert!(NetworkConfigBuilder::new().dropout_rate(1.0).is_err());
+}
+
+/// Records every update into a hidden neuron as `(proposed_delta, ED rule at unit rate)`
+#[derive(Clone, Default)]
+struct HiddenUpdates(std::sync::Arc<std::sync::Mutex<Vec<(f64, f64)>>>);
+
+impl ConnectionUpdateHook for HiddenUpdates {
+    fn adjust_delta(&mut self, ctx: UpdateContext) -> f64 {
+        if ctx.to_layer == LayerType::Hidden {
+            let rule = Connection::ed_weight_delta(1.0, ctx.error_signal, ctx.from_type, ctx.to_type);
+            self.0.lock().unwrap().push((ctx.proposed_delta, rule));
+        }
+        ctx.proposed_delta
+    }
+}
+
+#[test]
+fn test_dropout_keeps_update_direction() {
+    let config = NetworkConfig {
+        dropout_rate: 0.5,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 20, 1), config, 4).unwrap();
+    let updates = HiddenUpdates::default();
+    network.set_update_hook(updates.clone());
+
+    let mut scaled_survivors = 0;
+    for pattern in TrainingPattern::create_xor_dataset().iter().cycle().take(200) {
+        network.train_pattern(pattern).unwrap();
+        scaled_survivors += network.layers[2].neurons.iter().filter(|n| n.output > 1.0).count();
+    }
+    assert!(scaled_survivors > 0);
+
+    // Sources never output negative values, so each delta follows the sign of the rule
+    let updates = updates.0.lock().unwrap();
+    assert!(updates.iter().any(|&(delta, _)| delta != 0.0));
+    assert!(updates.iter().all(|&(delta, rule)| delta * rule >= 0.0));
+}
+
+#[test]
+fn test_weight_noise_only_perturbs_forward_passes() {
+    let trained = |weight_noise_std| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(NetworkConfig {
+                weight_noise_std,
+                ..NetworkConfig::default()
+            })
+            .seed(21)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        network.train(20).unwrap();
+        network
+    };
+    let xor = TrainingPattern::create_xor_dataset();
+
+    let clean = trained(0.0);
+    let mut rng = StdRng::seed_from_u64(1);
+    assert_eq!(
+        clean.predict_with_noise(&[1.0, 0.0], &mut rng).unwrap(),
+        clean.predict(&[1.0, 0.0]).unwrap()
+    );
+    assert_eq!(
+        clean.evaluate_with_noise(&xor, 0.4, &mut rng).unwrap(),
+        clean.evaluate(&xor, 0.4).unwrap()
+    );
+
+    let mut noisy = trained(0.3);
+    assert_ne!(weights(&noisy), weights(&clean));
+    let before = weights(&noisy);
+    let run = |seed| noisy.predict_with_noise(&[1.0, 0.0], &mut StdRng::seed_from_u64(seed)).unwrap();
+    assert_eq!(run(1), run(1));
+    assert_ne!(run(1), run(2));
+    assert_ne!(run(1), noisy.predict(&[1.0, 0.0]).unwrap());
+    assert_eq!(weights(&noisy), before);
+
+    // `forward` stays clean like `predict`
+    let predicted = noisy.predict(&[0.0, 1.0]).unwrap();
+    assert_eq!(noisy.forward(&[0.0, 1.0]).unwrap(), predicted);
+
+    noisy.config.weight_noise_std = -1.0;
+    assert!(matches!(noisy.config.validate(), Err(EdError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_single_training_step_reduces_error() {
+    let mut rng = StdRng::seed_from_u64(17);
+    let (mut decreased, mut total_change) = (0, 0.0);
+
+    for seed in 0..200 {
+        let inputs = vec![rng.random::<f64>(), rng.random::<f64>(), rng.random::<f64>()];
+        let targets = vec![f64::from(u8::from(rng.random::<bool>())), rng.random::<f64>()];
+        let pattern = TrainingPattern::new(inputs, targets, 0);
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(3, 6, 2), NetworkConfig::default(), seed).unwrap();
+
+        let (before, after) = network.verify_error_decrease(&pattern).unwrap();
+        assert!(before.is_finite() && after.is_finite());
+        decreased += usize::from(after < before);
+        total_change += after - before;
+    }
+
+    // A sign error in the diffusion direction would make most steps worse
+    assert!(decreased > 150, "only {} of 200 steps reduced the error", decreased);
+    assert!(total_change < 0.0);
+
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(3, 6, 2), NetworkConfig::default(), 0).unwrap();
+    let wrong = TrainingPattern::new(vec![0.0; 3], vec![1.0], 0);
+    assert!(matches!(
+        network.verify_error_decrease(&wrong),
+        Err(EdError::DimensionMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_lateral_inhibition_decorrelates_hidden_units() {
+    let mean_correlation = |lateral_inhibition| {
+        let config = NetworkConfig {
+            lateral_inhibition,
+            ..NetworkConfig::default()
+        };
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(3, 6, 1), config, 1).unwrap();
+        let mut rng = StdRng::seed_from_u64(5);
+        let activations: Vec<Vec<f64>> = (0..100)
+            .map(|_| {
+                let inputs = [rng.random::<f64>(), rng.random::<f64>(), rng.random::<f64>()];
+                network.forward(&inputs).unwrap();
+                network.layers[2].neurons.iter().map(|n| n.output).collect()
+            })
+            .collect();
+
+        let column = |unit: usize| activations.iter().map(|row| row[unit]).collect::<Vec<f64>>();
+        let correlation = |a: &[f64], b: &[f64]| {
+            let mean = |v: &[f64]| v.iter().sum::<f64>() / v.len() as f64;
+            let (ma, mb) = (mean(a), mean(b));
+            let cov: f64 = a.iter().zip(b).map(|(x, y)| (x - ma) * (y - mb)).sum();
+            let var = |v: &[f64], m: f64| v.iter().map(|x| (x - m).powi(2)).sum::<f64>();
+            cov / (var(a, ma) * var(b, mb)).sqrt()
+        };
+        let mut total = 0.0;
+        let mut pairs = 0;
+        for i in 0..6 {
+            for j in i + 1..6 {
+                total += correlation(&column(i), &column(j)).abs();
+                pairs += 1;
+            }
+        }
+        (total / pairs as f64, network)
+    };
+
+    let (plain, _) = mean_correlation(None);
+    let (inhibited, mut network) = mean_correlation(Some(2.0));
+    assert!(inhibited < plain, "{} >= {}", inhibited, plain);
+
+    // The lateral weights are fixed: present, negative and untouched by training
+    let hidden = network.layer_range(LayerType::Hidden);
+    let lateral = |network: &EDNetwork| {
+        hidden
+            .clone()
+            .flat_map(|from| hidden.clone().filter(move |&to| to != from).map(move |to| (from, to)))
+            .map(|(from, to)| network.connections[from][to].clone())
+            .collect::<Vec<Connection>>()
+    };
+    let before = lateral(&network);
+    assert!(before.iter().all(|c| c.connection_enabled && !c.trainable && c.weight == -2.0));
+    network.training_data = TrainingPattern::create_xor_dataset()
+        .into_iter()
+        .map(|p| TrainingPattern::new(vec![p.inputs[0], p.inputs[1], 1.0], p.targets, p.id))
+        .collect();
+    network.train(5).unwrap();
+    assert!(lateral(&network).iter().all(|c| c.weight == -2.0));
+    assert!(network.validate_sign_constraints().is_empty());
+}
+
+#[test]
+fn test_evaluate_trained_xor() {
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(8)
+        .outputs(1)
+        .seed(21)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    network.train(2000).unwrap();
+    let stats_before = network.stats.clone();
+
+    let xor = TrainingPattern::create_xor_dataset();
+    let report = network.evaluate(&xor, 0.4).unwrap();
+    assert_eq!(report.accuracy, 100.0);
+    assert_eq!(report.per_pattern_errors.len(), 4);
+    assert!(report.per_pattern_errors.iter().all(|&error| error <= 0.4));
+    let mse = report.per_pattern_errors.iter().map(|e| e * e).sum::<f64>() / 4.0;
+    assert!((report.mean_squared_error - mse).abs() < 1e-12);
+    assert_eq!(network.stats.error_history, stats_before.error_history);
+
+    assert_eq!(network.evaluate(&xor, 0.0).unwrap().accuracy, 0.0);
+    assert_eq!(network.evaluate(&[], 0.4), Err(EdError::EmptyTrainingSet));
+}
+
+#[test]
+fn test_momentum_speeds_up_xor() {
+    let epochs = |seed, momentum| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(NetworkConfig {
+                momentum,
+                ..NetworkConfig::default()
+            })
+            .seed(seed)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        let stats = network.train(2000).unwrap();
+        assert!(stats.converged);
+        stats.epoch
+    };
+    for seed in [3, 5, 7] {
+        assert!(epochs(seed, 0.5) < epochs(seed, 0.0), "seed {}", seed);
+    }
+
+    let mut connection = Connection::new(0, 1, 0.5, NeuronType::Excitatory, NeuronType::Excitatory);
+    assert_eq!(connection.momentum_delta(0.1, 0.0), 0.1);
+    assert_eq!(connection.momentum_delta(0.2, 0.5), 0.25);
+    assert_eq!(connection.previous_delta, 0.25);
+
+    // Networks saved before momentum existed still load
+    let old = json::to_string(&connection).unwrap().replace(",\"previous_delta\":0.25", "");
+    assert_eq!(json::from_str::<Connection>(&old).unwrap().previous_delta, 0.0);
+
+    let config = NetworkConfig {
+        momentum: 1.0,
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(2, 2, 1), config),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_sign_constraint_violations_detected_and_enforced() {
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), NetworkConfig::default(), 3).unwrap();
+    assert!(network.validate_sign_constraints().is_empty());
+
+    let hidden = network.layer_range(LayerType::Hidden).start;
+    let output = network.layer_range(LayerType::Output).start;
+    let weight = -network.connections[hidden][output].weight;
+    network.connections[hidden][output].weight = weight;
+    let violations = network.validate_sign_constraints();
+    assert_eq!(violations.len(), 1);
+    assert_eq!(
+        (violations[0].from, violations[0].to, violations[0].weight),
+        (hidden, output, weight)
+    );
+    assert_eq!(violations[0].expected_sign, -weight.signum());
+
+    for update_mode in [UpdateMode::Online, UpdateMode::Batch] {
+        let trained = |enforce_sign_constraints| {
+            let mut network = NetworkBuilder::new()
+                .inputs(2)
+                .hidden(6)
+                .outputs(1)
+                .config(NetworkConfig {
+                    learning_rate: 5.0,
+                    enforce_sign_constraints,
+                    update_mode,
+                    ..NetworkConfig::default()
+                })
+                .seed(2)
+                .training_data(TrainingPattern::create_xor_dataset())
+                .build()
+                .unwrap();
+            network.set_update_hook(ScaleHook(-1.0));
+            network.train(20).unwrap();
+            network
+        };
+
+        assert!(!trained(false).validate_sign_constraints().is_empty(), "{:?}", update_mode);
+        assert!(trained(true).validate_sign_constraints().is_empty(), "{:?}", update_mode);
+    }
+}
+
+#[test]
+fn test_weight_bounds_follow_connection_sign() {
+    let mut connection = Connection::new(0, 1, 0.0, NeuronType::Excitatory, NeuronType::Excitatory);
+    connection.weight = 3.0;
+    assert!(connection.clamp_to_bounds((0.1, 2.0), 1.0));
+    assert_eq!(connection.weight, 2.0);
+    connection.weight = -0.05;
+    assert!(connection.clamp_to_bounds((0.1, 2.0), -1.0));
+    assert_eq!(connection.weight, -0.1);
+    assert!(!connection.clamp_to_bounds((0.1, 2.0), -1.0));
+
+    for update_mode in [UpdateMode::Online, UpdateMode::Batch] {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(6)
+            .outputs(1)
+            .config(NetworkConfig {
+                learning_rate: 5.0,
+                weight_init_range: 0.1,
+                threshold_init_range: 0.1,
+                weight_bounds: Some((0.0, 0.3)),
+                update_mode,
+                ..NetworkConfig::default()
+            })
+            .seed(2)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        // The dry run's step is discarded, its clips included
+        network.dry_run(&network.training_data.clone()).unwrap();
+        assert_eq!(network.stats.clipped_updates, 0);
+        network.train(50).unwrap();
+
+        assert!(network.stats.clipped_updates > 0, "{:?}", update_mode);
+        let types: Vec<f64> = network
+            .layers
+            .iter()
+            .flat_map(|layer| layer.neurons.iter().map(|n| n.neuron_type.as_weight_factor()))
+            .collect();
+        for connection in network.connections.iter().flatten().filter(|c| c.connection_enabled) {
+            let signed = connection.weight * types[connection.from] * types[connection.to];
+            assert!((0.0..=0.3).contains(&signed), "{:?}", connection);
+        }
+    }
+
+    let config = NetworkConfig {
+        weight_bounds: Some((0.5, 0.1)),
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(2, 2, 1), config),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_weight_clamp_is_symmetric() {
+    let mut connection = Connection::new(0, 1, 0.0, NeuronType::Excitatory, NeuronType::Inhibitory);
+    connection.weight = -3.0;
+    connection.clamp_weight(0.5);
+    assert_eq!(connection.weight, -0.5);
+    connection.weight = 0.2;
+    connection.clamp_weight(0.5);
+    assert_eq!(connection.weight, 0.2);
+
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(6)
+        .outputs(1)
+        .config(NetworkConfig {
+            learning_rate: 5.0,
+            weight_clamp: Some(0.2),
+            ..NetworkConfig::default()
+        })
+        .seed(2)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    network.train(500).unwrap();
+
+    assert!(network.stats.clipped_updates > 0);
+    let enabled: Vec<f64> = network
+        .connections
+        .iter()
+        .flatten()
+        .filter(|c| c.connection_enabled)
+        .map(|c| c.weight)
+        .collect();
+    assert!(enabled.iter().all(|weight| (-0.2..=0.2).contains(weight)));
+    // Both signs reach the limit, unlike the sign-preserving `weight_bounds`
+    assert!(enabled.contains(&0.2) && enabled.contains(&-0.2));
+
+    assert!(NetworkConfigBuilder::new().weight_clamp(-1.0).is_err());
+    assert_eq!(
+        NetworkConfigBuilder::new().weight_clamp(0.5).unwrap().build().weight_clamp,
+        Some(0.5)
+    );
+}
+
+#[test]
+fn test_layer_learning_rates_override_by_source_layer() {
+    let trained = |layer_learning_rates| {
+        let config = NetworkConfig {
+            layer_learning_rates,
+            ..NetworkConfig::default()
+        };
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), config, 6).unwrap();
+        network.train_pattern(&TrainingPattern::new(vec![1.0, 0.0], vec![1.0], 0)).unwrap();
+        network
+    };
+
+    let baseline = trained(LayerLearningRates::default());
+    let explicit = trained(LayerLearningRates {
+        bias: Some(0.8),
+        input: Some(0.8),
+        hidden: Some(0.8),
+        output: Some(0.8),
+    });
+    assert_eq!(weights(&explicit), weights(&baseline));
+
+    let hidden_only = trained(LayerLearningRates {
+        hidden: Some(0.1),
+        ..LayerLearningRates::default()
+    });
+    // Only rows leaving the hidden layer see the override
+    let hidden = baseline.layer_range(LayerType::Hidden);
+    let changed: Vec<usize> = (0..baseline.connections.len())
+        .filter(|&from| {
+            let row = |network: &EDNetwork| network.connections[from].iter().map(|c| c.weight).collect::<Vec<f64>>();
+            row(&hidden_only) != row(&baseline)
+        })
+        .collect();
+    assert!(!changed.is_empty());
+    assert!(changed.iter().all(|from| hidden.contains(from)), "{:?}", changed);
+
+    let config = NetworkConfig {
+        layer_learning_rates: LayerLearningRates {
+            input: Some(-1.0),
+            ..LayerLearningRates::default()
+        },
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(2, 2, 1), config),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_adaptive_learning_rate_follows_error() {
+    let schedule = LearningSchedule::AdaptiveLr {
+        increase_factor: 1.05,
+        decrease_factor: 0.5,
+        min_lr: 0.1,
+        max_lr: 1.0,
+    };
+    let config = NetworkConfig {
+        schedule,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), config, 3).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    for _ in 0..30 {
+        network.train_epoch().unwrap();
+    }
+
+    let rates = &network.stats.learning_rate_history;
+    let errors = &network.stats.error_history;
+    assert_eq!(rates.len(), 30);
+    assert_eq!(&rates[..2], &[0.8, 0.8]);
+    for epoch in 2..rates.len() {
+        let expected = if errors[epoch - 1] < errors[epoch - 2] {
+            rates[epoch - 1] * 1.05
+        } else if errors[epoch - 1] > errors[epoch - 2] {
+            rates[epoch - 1] * 0.5
+        } else {
+            rates[epoch - 1]
+        };
+        assert!((rates[epoch] - expected.clamp(0.1, 1.0)).abs() < 1e-12, "epoch {}", epoch);
+    }
+
+    let config = NetworkConfig {
+        schedule: LearningSchedule::AdaptiveLr {
+            increase_factor: 0.9,
+            decrease_factor: 0.5,
+            min_lr: 0.1,
+            max_lr: 1.0,
+        },
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(
+        EDNetwork::new(NetworkDimensions::new(2, 2, 1), config),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_normalize_inputs_min_max() {
+    let mut patterns = vec![
+        TrainingPattern::new(vec![10.0, -4.0, 3.0], vec![0.0], 0),
+        TrainingPattern::new(vec![30.0, 0.0, 3.0], vec![1.0], 1),
+        TrainingPattern::new(vec![20.0, 4.0, 3.0], vec![1.0], 2),
+    ];
+    let params = TrainingPattern::normalize_inputs(&mut patterns);
+    assert_eq!(params, vec![(10.0, 30.0), (-4.0, 4.0), (3.0, 3.0)]);
+    assert_eq!(patterns[0].inputs, vec![0.0, 0.0, 0.0]);
+    assert_eq!(patterns[1].inputs, vec![1.0, 0.5, 0.0]);
+    assert_eq!(patterns[2].inputs, vec![0.5, 1.0, 0.0]);
+    assert_eq!(patterns[1].targets, vec![1.0]);
+
+    // Inference inputs reuse the fitted ranges
+    let mut inputs = vec![25.0, 8.0, 7.0];
+    TrainingPattern::apply_normalization(&mut inputs, &params);
+    assert_eq!(inputs, vec![0.75, 1.5, 0.0]);
+    assert!(TrainingPattern::normalize_inputs(&mut []).is_empty());
+}
+
+#[test]
+fn test_train_test_split() {
+    let patterns = TrainingPattern::create_parity_dataset(4);
+    let (train, test) = split(patterns.clone(), 0.25, &mut StdRng::seed_from_u64(9)).unwrap();
+    assert_eq!((train.len(), test.len()), (12, 4));
+
+    // Every original id lands on exactly one side, paired with its own pattern
+    let mut ids: Vec<usize> = train.iter().chain(&test).map(|p| p.id).collect();
+    ids.sort_unstable();
+    assert_eq!(ids, (0..16).collect::<Vec<_>>());
+    assert!(train.iter().chain(&test).all(|p| p.inputs == patterns[p.id].inputs));
+
+    // Seeded RNGs split deterministically
+    let (_, again) = split(patterns.clone(), 0.25, &mut StdRng::seed_from_u64(9)).unwrap();
+    assert_eq!(
+        test.iter().map(|p| p.id).collect::<Vec<_>>(),
+        again.iter().map(|p| p.id).collect::<Vec<_>>()
+    );
+
+    let mut rng = StdRng::seed_from_u64(9);
+    for fraction in [0.0, 1.0, -0.5, f64::NAN] {
+        assert!(matches!(
+            split(patterns.clone(), fraction, &mut rng),
+            Err(EdError::InvalidConfig(_))
+        ));
+    }
+    let xor = TrainingPattern::create_xor_dataset();
+    assert!(matches!(split(xor.clone(), 0.1, &mut rng), Err(EdError::InvalidConfig(_))));
+    assert!(matches!(split(xor, 0.9, &mut rng), Err(EdError::InvalidConfig(_))));
+    assert!(matches!(split(Vec::new(), 0.5, &mut rng), Err(EdError::EmptyTrainingSet)));
+}
+
+#[test]
+fn test_normalizer_min_max_and_z_score() {
+    let patterns = vec![
+        TrainingPattern::new(vec![0.0, 7.0, 1024.0], vec![10.0], 0),
+        TrainingPattern::new(vec![4096.0, 7.0, 2048.0], vec![30.0], 1),
+        TrainingPattern::new(vec![2048.0, 7.0, 3072.0], vec![20.0], 2),
+    ];
+
+    let min_max = Normalizer::fit(&patterns, NormalizationMethod::MinMax, false).unwrap();
+    let mut scaled = patterns.clone();
+    min_max.transform(&mut scaled);
+    assert_eq!(scaled[1].inputs, vec![1.0, 0.0, 0.5]);
+    assert_eq!(scaled[2].inputs, vec![0.5, 0.0, 1.0]);
+    assert_eq!(scaled[1].targets, vec![30.0]);
+    assert_eq!(min_max.transform_input(&[1024.0, 7.0, 1024.0]), vec![0.25, 0.0, 0.0]);
+    // Training patterns scale exactly as `normalize_inputs` scales them
+    let mut direct = patterns.clone();
+    TrainingPattern::normalize_inputs(&mut direct);
+    assert!(direct.iter().zip(&scaled).all(|(direct, scaled)| direct.inputs == scaled.inputs));
+
+    // Z-scores have zero mean and unit variance; the constant column stays finite
+    let z_score = Normalizer::fit(&patterns, NormalizationMethod::ZScore, true).unwrap();
+    let mut standardized = patterns.clone();
+    z_score.transform(&mut standardized);
+    for column in 0..3 {
+        let values: Vec<f64> = standardized.iter().map(|p| p.inputs[column]).collect();
+        let mean = values.iter().sum::<f64>() / 3.0;
+        let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / 3.0;
+        assert!(mean.abs() < 1e-12);
+        assert!(values.iter().all(|v| v.is_finite()));
+        assert!(
+            (variance
+                - if column == 1 {
+                    0.0
+                } else {
+                    1.0
+                })
+            .abs()
+                < 1e-12
+        );
+    }
+    assert!(standardized[0].targets[0] < 0.0 && standardized[1].targets[0] > 0.0);
+    let restored = z_score.inverse_transform_targets(&standardized[1].targets);
+    assert!((restored[0] - 30.0).abs() < 1e-12);
+
+    // Survives a JSON round trip for use at inference time
+    let loaded: Normalizer = json::from_str(&json::to_string(&z_score).unwrap()).unwrap();
+    assert_eq!(loaded, z_score);
+
+    assert!(matches!(
+        Normalizer::fit(&[], NormalizationMethod::MinMax, false),
+        Err(EdError::EmptyTrainingSet)
+    ));
+    let ragged = vec![patterns[0].clone(), TrainingPattern::new(vec![1.0], vec![0.0], 1)];
+    assert!(matches!(
+        Normalizer::fit(&ragged, NormalizationMethod::ZScore, false),
+        Err(EdError::DimensionMismatch {
+            expected: 3,
+            got: 1
+        })
+    ));
+}
+
+#[test]
+fn test_step_decay_halves_learning_rate() {
+    let config = NetworkConfig {
+        schedule: LearningSchedule::StepDecay {
+            factor: 0.5,
+            every: 3,
+        },
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), config, 1).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    for _ in 0..7 {
+        network.train_epoch().unwrap();
+    }
+    assert_eq!(network.stats.learning_rate_history, vec![0.8, 0.8, 0.8, 0.4, 0.4, 0.4, 0.2]);
+
+    network.config.schedule = LearningSchedule::ExponentialDecay {
+        gamma: 0.5,
+    };
+    assert_eq!(network.current_learning_rate(), 0.8 * 0.5_f64.powi(7));
+
+    network.config.schedule = LearningSchedule::StepDecay {
+        factor: 0.5,
+        every: 0,
+    };
+    assert!(matches!(network.train_epoch(), Err(EdError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_softmax_output_layer_learns_three_classes() {
+    let class = |inputs: Vec<f64>, label: usize, id| {
+        let mut targets = vec![0.0; 3];
+        targets[label] = 1.0;
+        TrainingPattern::new(inputs, targets, id)
+    };
+    let patterns = vec![
+        class(vec![1.0, 0.0, 0.0], 0, 0),
+        class(vec![0.0, 1.0, 0.0], 1, 1),
+        class(vec![0.0, 0.0, 1.0], 2, 2),
+        class(vec![1.0, 1.0, 0.0], 1, 3),
+    ];
+    let config = NetworkConfig {
+        output_layer: OutputActivation::Softmax,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(3, 8, 3), config, 3).unwrap();
+    network.training_data = patterns.clone();
+    network.train(2000).unwrap();
+
+    assert!(network.stats.converged, "error {}", network.stats.total_error);
+    assert_eq!(network.stats.accuracy, 100.0);
+    for pattern in &patterns {
+        let outputs = network.predict(&pattern.inputs).unwrap();
+        assert!((outputs.iter().sum::<f64>() - 1.0).abs() < 1e-12);
+        assert_eq!(argmax(&outputs), argmax(&pattern.targets));
+    }
+    assert_eq!(network.evaluate(&patterns, 0.0).unwrap().accuracy, 100.0);
+    assert_eq!(argmax(&[0.2, 0.7, 0.7]), Some(1));
+}
+
+#[test]
+fn test_thresholds_train_unless_frozen() {
+    let trained = |freeze_thresholds| {
+        let config = NetworkConfig {
+            freeze_thresholds,
+            ..NetworkConfig::default()
+        };
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), config, 8).unwrap();
+        let initial: Vec<Option<f64>> = (0..network.dimensions.total_neurons).map(|i| network.threshold(i)).collect();
+        network.training_data = TrainingPattern::create_xor_dataset();
+        network.train(20).unwrap();
+        let learned: Vec<Option<f64>> = (0..network.dimensions.total_neurons).map(|i| network.threshold(i)).collect();
+        (network, initial, learned)
+    };
+
+    let (network, initial, learned) = trained(false);
+    // Bias and input neurons are clamped and have no threshold
+    assert!(initial[..6].iter().all(Option::is_none));
+    assert!(initial[6..].iter().all(Option::is_some));
+    assert_eq!(network.threshold(network.dimensions.total_neurons), None);
+    assert_ne!(initial, learned);
+
+    let (network, initial, learned) = trained(true);
+    assert_eq!(initial, learned);
+    assert_ne!(weights(&network), weights(&trained(false).0));
+}
+
+#[test]
+fn test_weight_decay_shrinks_weights_without_error_signal() {
+    let config = NetworkConfig {
+        weight_decay: 0.1,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 4, 1), config, 4).unwrap();
+    network.training_data = TrainingPattern::create_xor_dataset();
+    // A zero-scaling hook silences the ED rule so only decay moves the weights
+    network.set_update_hook(ScaleHook(0.0));
+    let initial = weights(&network);
+    for _ in 0..5 {
+        network.train_epoch().unwrap();
+    }
+
+    let expected = 0.9_f64.powi(5);
+    for (weight, start) in weights(&network).iter().zip(&initial) {
+        assert!((weight - start * expected).abs() < 1e-12);
+    }
+    assert!(weights(&network).iter().any(|&w| w != 0.0));
+
+    network.config.weight_decay = 1.0;
+    assert!(matches!(network.train_epoch(), Err(EdError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_weight_stats_on_fresh_network() {
+    let config = NetworkConfig {
+        weight_init_range: 0.5,
+        threshold_init_range: 0.5,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(NetworkDimensions::new(2, 6, 2), config, 8).unwrap();
+    let stats = network.weight_stats();
+
+    let enabled = network.connections.iter().flatten().filter(|c| c.connection_enabled).count();
+    assert_eq!(stats.enabled_connections, enabled);
+    assert!(-0.5 <= stats.min && stats.min <= stats.mean && stats.mean <= stats.max && stats.max <= 0.5);
+    assert!(stats.std_dev > 0.0 && stats.std_dev <= 0.5);
+
+    network.forward(&[0.5, 0.5]).unwrap();
+    let outputs = &network.layers[3].neurons;
+    let saturated = outputs.iter().filter(|n| n.output < 0.01 || n.output > 0.99).count();
+    assert_eq!(network.weight_stats().saturated_outputs, saturated);
+    assert!(network.weight_stats().to_string().starts_with(&format!("Weights:{}", enabled)));
+}
+
+#[test]
+fn test_hidden_ei_ratio_trains_xor() {
+    let config = NetworkConfig {
+        ei_ratios: LayerEiRatios {
+            hidden: Some(0.75),
+            ..LayerEiRatios::default()
+        },
+        ..NetworkConfig::default()
+    };
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(8)
+        .outputs(1)
+        .config(config)
+        .seed(21)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+
+    let hidden = &network.layers[2].neurons;
+    assert_eq!(hidden.iter().filter(|neuron| neuron.is_excitatory()).count(), 6);
+    assert!(hidden[..6].iter().all(|neuron| neuron.is_excitatory()));
+    // Connection::new keeps applying the type signs: inhibitory hidden neurons only send negative weights
+    let range = network.layer_range(LayerType::Hidden);
+    for from in range.clone().skip(6) {
+        assert!(network.connections[from].iter().all(|c| c.weight <= 0.0 || !c.connection_enabled));
+    }
+
+    network.train(2000).unwrap();
+    assert!(network.stats.converged);
+
+    let invalid = NetworkConfig {
+        ei_ratios: LayerEiRatios {
+            hidden: Some(1.5),
+            ..LayerEiRatios::default()
+        },
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(invalid.validate(), Err(EdError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_config_presets_train_xor() {
+    let xor = |config| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(config)
+            .seed(21)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        network.train(2000).unwrap();
+        network
+    };
+
+    let kaneko = xor(NetworkConfig::kaneko_default());
+    assert!(kaneko.stats.converged);
+    assert_eq!(weights(&kaneko), weights(&xor(NetworkConfig::default())));
+
+    let fast = xor(NetworkConfig::fast_convergence());
+    assert!(fast.stats.converged);
+    assert!(fast.stats.epoch < kaneko.stats.epoch);
+    // One timestep fewer would leave the outputs out of reach of the inputs
+    let mut one_step = EDNetwork::new_seeded(
+        NetworkDimensions::new(2, 4, 1),
+        NetworkConfig {
+            timesteps: 1,
+            ..NetworkConfig::fast_convergence()
+        },
+        21,
+    )
+    .unwrap();
+    assert!(matches!(
+        one_step.dry_run(&TrainingPattern::create_xor_dataset()),
+        Err(EdError::InvalidTopology(_))
+    ));
+
+    let stable = xor(NetworkConfig::stable());
+    assert!(stable.stats.converged);
+    assert!(stable.stats.epoch > kaneko.stats.epoch);
+
+    // Hidden-to-hidden and output feedback connections exist only in the recurrent preset
+    let deep = xor(NetworkConfig::deep());
+    assert!(deep.stats.converged);
+    let hidden = deep.layer_range(LayerType::Hidden);
+    let feedback = |network: &EDNetwork| {
+        hidden
+            .clone()
+            .chain(network.layer_range(LayerType::Output))
+            .flat_map(|from| hidden.clone().map(move |to| (from, to)))
+            .filter(|&(from, to)| from != to && network.connections[from][to].connection_enabled)
+            .count()
+    };
+    assert!(feedback(&deep) > 0);
+    assert_eq!(feedback(&kaneko), 0);
+
+    // Without the inhibitory input copies XOR is out of reach, but training stays well-behaved
+    let strict = xor(NetworkConfig::feedforward_strict());
+    assert!(!strict.config.flag_inhibitory_inputs);
+    assert!(strict.stats.total_error.is_finite());
+    assert!(strict.stats.accuracy >= 75.0);
+}
+
+#[test]
+fn test_train_with_callback_reports_and_aborts() {
+    let xor = || {
+        NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .seed(21)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap()
+    };
+
+    let mut network = xor();
+    let mut seen = Vec::new();
+    network
+        .train_with_callback(2000, |stats| {
+            seen.push(stats.epoch);
+            stats.epoch < 5
+        })
+        .unwrap();
+    assert_eq!(seen, vec![1, 2, 3, 4, 5]);
+    assert_eq!(network.stats.stop_reason, Some(StopReason::Aborted));
+    assert!(!network.stats.converged);
+
+    // A callback that never aborts matches plain `train`
+    let mut plain = xor();
+    plain.train(2000).unwrap();
+    let mut calls = 0;
+    let mut watched = xor();
+    watched
+        .train_with_callback(2000, |_| {
+            calls += 1;
+            true
+        })
+        .unwrap();
+    assert_eq!(calls, plain.stats.epoch);
+    assert_eq!(watched.stats.stop_reason, Some(StopReason::Converged));
+    assert_eq!(weights(&watched), weights(&plain));
+}
+
+#[test]
+fn test_early_stopping_on_training_plateau() {
+    // XOR is out of reach without inhibitory inputs, so the training error stalls
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(8)
+        .outputs(1)
+        .config(NetworkConfig::feedforward_strict())
+        .seed(21)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    let stats = network
+        .train_until(StoppingCriteria::epochs(2000).early_stopping(20, 1e-3))
+        .unwrap();
+    assert_eq!(stats.stop_reason, Some(StopReason::EarlyStop));
+    assert!(!stats.converged);
+    assert!(stats.epoch > 20 && stats.epoch < 2000, "{}", stats.epoch);
+
+    // The last `patience` epochs never beat the best error by more than `min_delta`
+    let history = &stats.error_history;
+    let (before, plateau) = history.split_at(history.len() - 20);
+    let best = before.iter().copied().fold(f64::INFINITY, f64::min);
+    assert!(plateau.iter().all(|&error| error >= best - 1e-3));
+
+    assert!(matches!(
+        network.train_until(StoppingCriteria::epochs(10).early_stopping(0, 1e-3)),
+        Err(EdError::InvalidConfig(_))
+    ));
+}
+
+#[test]
+fn test_config_validation_rejects_each_field() {
+    assert_eq!(NetworkConfig::default().validate(), Ok(()));
+
+    let rejected: Vec<(&str, NetworkConfig)> = vec![
+        (
+            "timesteps",
+            NetworkConfig {
+                timesteps: 0,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "learning_rate",
+            NetworkConfig {
+                learning_rate: -0.5,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "sigmoid_steepness",
+            NetworkConfig {
+                sigmoid_steepness: 0.0,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "weight_init_range",
+            NetworkConfig {
+                weight_init_range: -1.0,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "threshold_init_range",
+            NetworkConfig {
+                threshold_init_range: f64::NAN,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "convergence_threshold",
+            NetworkConfig {
+                convergence_threshold: -1.0,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "error_amplification",
+            NetworkConfig {
+                error_amplification: f64::INFINITY,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "bias",
+            NetworkConfig {
+                bias: f64::NAN,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "momentum",
+            NetworkConfig {
+                momentum: 1.0,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "weight_decay",
+            NetworkConfig {
+                weight_decay: -0.1,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "weight_noise_std",
+            NetworkConfig {
+                weight_noise_std: -0.1,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "dropout_rate",
+            NetworkConfig {
+                dropout_rate: -0.1,
+                ..NetworkConfig::default()
+            },
+        ),
+        (
+            "weight_clamp",
+            NetworkConfig {
+                weight_clamp: Some(0.0),
+                ..NetworkConfig::default()
+            },
+        ),
+    ];
+    for (field, config) in rejected {
+        assert!(
+            matches!(config.validate(), Err(EdError::InvalidConfig(msg)) if msg.contains(field)),
+            "{}",
+            field
+        );
+        assert!(EDNetwork::new(NetworkDimensions::new(2, 2, 1), config).is_err(), "{}", field);
+    }
+
+    let config = NetworkConfigBuilder::new()
+        .timesteps(3)
+        .and_then(|builder| builder.learning_rate(0.5))
+        .and_then(|builder| builder.momentum(0.2))
+        .unwrap()
+        .build();
+    assert_eq!((config.timesteps, config.learning_rate, config.momentum), (3, 0.5, 0.2));
+
+    assert!(NetworkConfigBuilder::new().timesteps(0).is_err());
+    assert!(NetworkConfigBuilder::new().learning_rate(0.0).is_err());
+    assert!(NetworkConfigBuilder::new().sigmoid_steepness(-0.4).is_err());
+    assert!(NetworkConfigBuilder::new().weight_init_range(-0.1).is_err());
+    assert!(NetworkConfigBuilder::new().convergence_threshold(-1.0).is_err());
+    assert!(NetworkConfigBuilder::from_config(NetworkConfig::fast_convergence()).is_ok());
+}
+
+#[test]
+fn test_loss_gradients() {
+    assert_eq!(Loss::Mse.gradient(0.25, 1.0), 0.75);
+    assert_eq!(Loss::Mse.gradient(0.75, 0.0), -0.75);
+    // (t - p) / (p (1 - p))
+    assert!((Loss::CrossEntropy.gradient(0.5, 1.0) - 2.0).abs() < 1e-12);
+    assert!((Loss::CrossEntropy.gradient(0.8, 0.0) + 5.0).abs() < 1e-12);
+    assert!((Loss::CrossEntropy.gradient(0.2, 1.0) - 5.0).abs() < 1e-12);
+    assert_eq!(Loss::CrossEntropy.gradient(0.3, 0.3), 0.0);
+    assert!(Loss::CrossEntropy.gradient(0.0, 1.0).is_finite());
+    assert!(Loss::CrossEntropy.gradient(1.0, 0.0).is_finite());
+    assert_eq!(NetworkConfig::default().loss, Loss::Mse);
+
+    // Cross-entropy with a softmax output layer learns the one-hot classes far faster than squared error
+    // once the inputs also reach the outputs directly
+    let epochs = |loss| {
+        let class = |inputs: Vec<f64>, label: usize, id| {
+            let mut targets = vec![0.0; 3];
+            targets[label] = 1.0;
+            TrainingPattern::new(inputs, targets, id)
+        };
+        let config = NetworkConfig {
+            loss,
+            output_layer: OutputActivation::Softmax,
+            flag_multilayer: false,
+            ..NetworkConfig::default()
+        };
+        let mut network = EDNetwork::new_seeded(NetworkDimensions::new(3, 8, 3), config, 2).unwrap();
+        network.training_data = vec![
+            class(vec![1.0, 0.0, 0.0], 0, 0),
+            class(vec![0.0, 1.0, 0.0], 1, 1),
+            class(vec![0.0, 0.0, 1.0], 2, 2),
+            class(vec![1.0, 1.0, 0.0], 1, 3),
+        ];
+        let stats = network.train(2000).unwrap();
+        assert!(stats.converged, "{:?}", loss);
+        stats.epoch
+    };
+    assert!(epochs(Loss::CrossEntropy) < epochs(Loss::Mse));
+}
+
+#[test]
+fn test_per_neuron_thresholds() {
+    let dimensions = NetworkDimensions::new(2, 4, 1);
+    let own_thresholds = |network: &EDNetwork| {
+        network
+            .layers
+            .iter()
+            .flat_map(|l| &l.neurons)
+            .map(|n| n.threshold)
+            .collect::<Vec<f64>>()
+    };
+
+    // Disabled by default: thresholds stay zero and seeded weights are unchanged
+    let plain = EDNetwork::new_seeded(dimensions.clone(), NetworkConfig::default(), 5).unwrap();
+    assert!(own_thresholds(&plain).iter().all(|&t| t == 0.0));
+
+    let config = NetworkConfig {
+        neuron_thresholds: true,
+        timesteps: 1,
+        threshold_init_range: 0.5,
+        ..NetworkConfig::default()
+    };
+    let mut network = EDNetwork::new_seeded(dimensions.clone(), config.clone(), 5).unwrap();
+    let without = NetworkConfig {
+        neuron_thresholds: false,
+        ..config.clone()
+    };
+    assert_eq!(
+        weights(&network),
+        weights(&EDNetwork::new_seeded(dimensions.clone(), without, 5).unwrap())
+    );
+    let initial = own_thresholds(&network);
+    assert!(initial[..6].iter().all(|&t| t == 0.0));
+    assert!(initial[6..].iter().all(|&t| t > 0.0 && t < 0.5));
+
+    // The threshold is subtracted from the weighted input before activation
+    let output = network.forward(&[1.0, 0.0]).unwrap()[0];
+    let sum: f64 = network
+        .layers
+        .iter()
+        .flat_map(|l| &l.neurons)
+        .take(6)
+        .enumerate()
+        .map(|(from, neuron)| neuron.output * network.connections[from][10].weight)
+        .sum();
+    assert!((output - sigmoid(sum - initial[10], 0.4)).abs() < 1e-12);
+
+    network.training_data = TrainingPattern::create_xor_dataset();
+    network.train(5).unwrap();
+    assert_ne!(own_thresholds(&network), initial);
+
+    let mut frozen = EDNetwork::new_seeded(
+        dimensions,
+        NetworkConfig {
+            freeze_thresholds: true,
+            ..config
+        },
+        5,
+    )
+    .unwrap();
+    frozen.training_data = TrainingPattern::create_xor_dataset();
+    frozen.train(5).unwrap();
+    assert_eq!(own_thresholds(&frozen), initial);
+}
+
+#[test]
+fn test_weight_init_distributions() {
+    let mut rng = StdRng::seed_from_u64(9);
+    let draws: Vec<f64> = (0..20_000).map(|_| random_gaussian(&mut rng, 0.5, 2.0)).collect();
+    let mean = draws.iter().sum::<f64>() / draws.len() as f64;
+    let variance = draws.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / draws.len() as f64;
+    assert!((mean - 0.5).abs() < 0.05, "{}", mean);
+    assert!((variance.sqrt() - 2.0).abs() < 0.05, "{}", variance);
+
+    let build = |weight_init| {
+        let config = NetworkConfig {
+            weight_init,
+            ..NetworkConfig::default()
+        };
+        EDNetwork::new_seeded(NetworkDimensions::new(6, 12, 1), config, 4).unwrap()
+    };
+    let magnitudes = |network: &EDNetwork, to: usize| {
+        network
+            .connections
+            .iter()
+            .map(move |row| &row[to])
+            .filter(|c| c.connection_enabled)
+            .map(|c| c.weight.abs())
+            .collect::<Vec<f64>>()
+    };
+
+    // Every connection into a neuron stays within 1 / sqrt(fan_in)
+    let scaled = build(Some(WeightInit::ScaledByFanIn));
+    for to in scaled.layer_range(LayerType::Hidden).chain(scaled.layer_range(LayerType::Output)) {
+        let incoming = magnitudes(&scaled, to);
+        let limit = 1.0 / (incoming.len() as f64).sqrt();
+        assert!(incoming.iter().all(|&w| w < limit), "neuron {}", to);
+    }
+
+    let uniform = build(Some(WeightInit::Uniform {
+        range: 0.1,
+    }));
+    assert!(uniform.connections.iter().flatten().all(|c| c.weight.abs() < 0.1));
+    assert_eq!(
+        weights(&build(None)),
+        weights(&EDNetwork::new_seeded(NetworkDimensions::new(6, 12, 1), NetworkConfig::default(), 4).unwrap())
+    );
+
+    let gaussian = build(Some(WeightInit::Gaussian {
+        mean: 0.0,
+        std: 0.3,
+    }));
+    let restored: EDNetwork = json::from_str(&json::to_string(&gaussian).unwrap()).unwrap();
+    assert_eq!(restored.config.weight_init, gaussian.config.weight_init);
+
+    let config = NetworkConfig {
+        weight_init: Some(WeightInit::Gaussian {
+            mean: 0.0,
+            std: -1.0,
+        }),
+        ..NetworkConfig::default()
+    };
+    assert!(matches!(config.validate(), Err(EdError::InvalidConfig(_))));
+}
+
+#[test]
+fn test_prune_weak_connections() {
+    let mut network = NetworkBuilder::new()
+        .inputs(2)
+        .hidden(8)
+        .outputs(1)
+        .seed(21)
+        .training_data(TrainingPattern::create_xor_dataset())
+        .build()
+        .unwrap();
+    network.train(2000).unwrap();
+    let enabled = |network: &EDNetwork| network.connections.iter().flatten().filter(|c| c.connection_enabled).count();
+    let before = enabled(&network);
+
+    let weak = network
+        .connections
+        .iter()
+        .flatten()
+        .filter(|c| c.connection_enabled && c.weight.abs() < 0.05)
+        .count();
+    assert_eq!(network.prune_weak_connections(0.05), weak);
+    assert_eq!(enabled(&network), before - weak);
+    assert_eq!(network.prune_weak_connections(0.05), 0);
+
+    // Pruned connections stay out of training
+    network.train(5).unwrap();
+    assert_eq!(enabled(&network), before - weak);
+    assert!(
+        network
+            .connections
+            .iter()
+            .flatten()
+            .filter(|c| !c.connection_enabled)
+            .all(|c| c.weight == 0.0)
+    );
+
+    assert_eq!(network.prune_weak_connections(f64::INFINITY), before - weak);
+    assert_eq!(enabled(&network), 0);
+    assert_eq!(network.predict(&[1.0, 0.0]).unwrap(), vec![0.5]);
+}
+
+#[test]
+fn test_network_reset_and_reset_weights() {
+    let build = || {
+        NetworkBuilder::new()
+            .inputs(2)
+            .hidden(4)
+            .outputs(1)
+            .seed(7)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap()
+    };
+    let mut network = build();
+    network.train(20).unwrap();
+    network.forward(&[1.0, 1.0]).unwrap();
+    let trained = weights(&network);
+
+    network.reset();
+    assert_eq!(weights(&network), trained);
+    assert_eq!(network.stats.epoch, 0);
+    assert!(network.stats.error_history.is_empty());
+    assert_eq!(network.stats.pattern_count, 4);
+    assert!(network.layers.iter().flat_map(|l| &l.neurons).all(|n| n.output == 0.0));
+
+    // Reseeding with the original seed replays the original run exactly
+    network.train(5).unwrap();
+    network.reset_weights(7).unwrap();
+    assert_eq!(network.stats.epoch, 0);
+    network.train(20).unwrap();
+    assert_eq!(weights(&network), trained);
+
+    network.reset_weights(8).unwrap();
+    assert_eq!(network.seed, Some(8));
+    assert_ne!(weights(&network), weights(&build()));
+}
+
+#[test]
+fn test_inference_timesteps_override() {
+    let build = |inference_timesteps| {
+        let mut network = NetworkBuilder::new()
+            .inputs(2)
+            .hidden(8)
+            .outputs(1)
+            .config(NetworkConfig {
+                inference_timesteps,
+                // Hidden loops keep the dynamics moving after the feedforward depth of two steps
+                flag_loop_cutting: false,
+                ..NetworkConfig::default()
+            })
+            .seed(21)
+            .training_data(TrainingPattern::create_xor_dataset())
+            .build()
+            .unwrap();
+        network.train(50).unwrap();
+        network
+    };
+    let plain = build(None);
+    let mut settled = build(Some(10));
+    // Training ignores the inference setting
+    assert_eq!(weights(&plain), weights(&settled));
+
+    let inputs = [1.0, 0.0];
+    assert_eq!(plain.predict(&inputs).unwrap(), plain.predict_with_timesteps(&inputs, 2).unwrap());
+    assert_eq!(
+        settled.predict(&inputs).unwrap(),
+        plain.predict_with_timesteps(&inputs, 10).unwrap()
+    );
+    assert_ne!(settled.predict(&inputs).unwrap(), plain.predict(&inputs).unwrap());
+    assert_eq!(settled.forward(&inputs).unwrap(), settled.predict(&inputs).unwrap());
+
+    assert!(matches!(plain.predict_with_timesteps(&inputs, 0), Err(EdError::InvalidConfig(_))));
+    settled.config.inference_timesteps = Some(0);
+    assert!(settled.config.validate().is_err());
+}
+
+#[test]
+fn test_connection_accessors() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(2, 3, 2), NetworkConfig::default()).unwrap();
+    assert_eq!(network.global_neuron_index(LayerType::Bias, 1), Some(1));
+    assert_eq!(network.global_neuron_index(LayerType::Input, 3), Some(5));
+    assert_eq!(network.global_neuron_index(LayerType::Hidden, 0), Some(6));
+    assert_eq!(network.global_neuron_index(LayerType::Output, 1), Some(10));
+    assert_eq!(network.global_neuron_index(LayerType::Output, 2), None);
+    assert_eq!(network.global_neuron_index(LayerType::Input, 4), None);
+
+    let hidden = network.global_neuron_index(LayerType::Hidden, 1).unwrap();
+    let output = network.global_neuron_index(LayerType::Output, 0).unwrap();
+    let connection = network.connection(hidden, output).unwrap();
+    assert_eq!((connection.from, connection.to), (hidden, output));
+    assert_eq!(connection.weight, network.connections[hidden][output].weight);
+
+    network.connection_mut(hidden, output).unwrap().weight = 0.25;
+    assert_eq!(network.connections[hidden][output].weight, 0.25);
+
+    let n = network.dimensions.total_neurons;
+    assert!(network.connection(n, 0).is_none());
+    assert!(network.connection(0, n).is_none());
+    assert!(network.connection_mut(n, n).is_none());
+}
+
+#[test]
+fn test_neuron_iteration_with_global_indices() {
+    let mut network = EDNetwork::new(NetworkDimensions::new(3, 4, 2), NetworkConfig::default()).unwrap();
+    let indices: Vec<usize> = network.neurons().map(|(index, _)| index).collect();
+    assert_eq!(indices, (0..network.dimensions.total_neurons).collect::<Vec<usize>>());
+
+    // Indices line up with the layer ranges used by the connection matrix
+    let hidden = network.layer_range(LayerType::Hidden);
+    let hidden_types: Vec<NeuronType> = network
+        .neurons()
+        .filter(|(index, _)| hidden.contains(index))
+        .map(|(_, n)| n.neuron_type)
+        .collect();
+    assert_eq!(
+        hidden_types,
+        network.layers[2].neurons.iter().map(|n| n.neuron_type).collect::<Vec<NeuronType>>()
+    );
+
+    for (index, neuron) in network.neurons_mut() {
+        neuron.output = index as f64;
+    }
+    assert_eq!(network.layers[3].neurons[1].output, 13.0);
 }